@@ -1,9 +1,55 @@
+use crate::ext::*;
+use crate::group::*;
 use crate::prime::*;
+#[cfg(feature = "rng")]
+use crate::rng::*;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::cell::OnceCell;
 
 pub const NOT_AN_NON_SINGULAR: &str = "not an non-singular...";
 pub const NOT_ON_THE_CURVE: &str = "not on the curve...";
+pub const INVALID_WINDOW: &str = "not a valid window size...";
+pub const OVERFLOW: &str = "overflowed...";
+pub const MISSING_FIELD: &str = "missing a field...";
+pub const NO_CURVE_FOUND: &str = "no curve found...";
+pub const SMALL_CHARACTERISTIC: &str = "not a prime greater than 3...";
+pub const WRONG_ORDER: &str = "not a multiple of the point order...";
+pub const OUT_OF_RANGE: &str = "not in the coordinate range...";
+pub const INVALID_ENCODING: &str = "not a valid point encoding...";
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CurveError {
+    NotOnCurve,
+    /// `-16 * (4a^3 + 27b^2)` over the integers, for the coefficients as given.
+    Singular(i128),
+    Field(String),
+}
+
+impl core::fmt::Display for CurveError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            CurveError::NotOnCurve => write!(f, "{}", NOT_ON_THE_CURVE),
+            CurveError::Singular(disc) => {
+                write!(f, "{} (discriminant {})", NOT_AN_NON_SINGULAR, disc)
+            }
+            CurveError::Field(err) => write!(f, "{}", err),
+        }
+    }
+}
+impl From<CurveError> for String {
+    fn from(err: CurveError) -> String {
+        err.to_string()
+    }
+}
+
+/// `Eq` and `Hash` compare the raw coordinates, so normalize points with
+/// `EllipticCurve::normalize` before using them as keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum Point {
     Inf,
     Affine(Num, Num),
@@ -16,9 +62,32 @@ impl Point {
             Point::Affine(_, _) => false,
         }
     }
+    /// Compares without branching on the coordinates. This is best effort
+    /// only: the field arithmetic that produced the points is variable-time.
+    pub fn ct_eq(&self, other: &Point) -> bool {
+        let limbs = |pt: &Point| match *pt {
+            Point::Inf => (1, 0, 0),
+            Point::Affine(x, y) => (0, x, y),
+        };
+        let (t0, x0, y0) = limbs(self);
+        let (t1, x1, y1) = limbs(other);
+        ((t0 ^ t1) | (x0 ^ x1) | (y0 ^ y1)) == 0
+    }
+    pub fn coordinates(&self) -> Option<(Num, Num)> {
+        match self {
+            Point::Inf => None,
+            Point::Affine(x, y) => Some((*x, *y)),
+        }
+    }
+    pub fn x(&self) -> Option<Num> {
+        self.coordinates().map(|(x, _)| x)
+    }
+    pub fn y(&self) -> Option<Num> {
+        self.coordinates().map(|(_, y)| y)
+    }
 }
-impl std::fmt::Display for Point {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::fmt::Display for Point {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         match self {
             Point::Inf => write!(f, "Inf"),
             Point::Affine(x, y) => write!(f, "({}, {})", x, y),
@@ -26,43 +95,554 @@ impl std::fmt::Display for Point {
     }
 }
 
+#[derive(Debug, Clone, Copy)]
+struct ProjectivePoint {
+    x: Num,
+    y: Num,
+    z: Num,
+}
+
+impl ProjectivePoint {
+    const INF: ProjectivePoint = ProjectivePoint { x: 0, y: 1, z: 0 };
+
+    fn neg(&self, prime: Num) -> ProjectivePoint {
+        ProjectivePoint {
+            x: self.x,
+            y: (-self.y).rem_euclid(prime),
+            z: self.z,
+        }
+    }
+}
+
 pub trait Op {
     fn lhs(&self, y: Num) -> Result<Num, String>;
     fn rhs(&self, x: Num) -> Result<Num, String>;
     fn is_on(&self, point: Point) -> bool;
+    fn is_on_affine(&self, x: Num, y: Num) -> bool;
+    /// Tests `Y^2 Z = X^3 + a X Z^2 + b Z^3` without inverting `z`; any
+    /// `z ≡ 0` is taken as the point at infinity.
+    fn is_on_projective(&self, x: Num, y: Num, z: Num) -> bool;
+    /// Unchecked counterpart to `represent`: reduces the coordinates into
+    /// `0..prime` without testing membership.
+    fn normalize(&self, point: Point) -> Point;
     fn represent(&self, point: Point) -> Result<Point, &str>;
+    fn checked_represent(&self, point: Point) -> Result<Point, CurveError>;
     fn inv(&self, point: Point) -> Result<Point, &str>;
     fn sum(&self, pt0: Point, pt1: Point) -> Result<Point, String>;
+    fn sum_explain(&self, pt0: Point, pt1: Point) -> Result<(Point, &'static str), String>;
+    fn sum_all(&self, points: &[Point]) -> Result<Point, String>;
+    /// Left fold of `sum` that validates each point once up front instead of
+    /// at every step.
+    fn sum_chain(&self, points: &[Point]) -> Result<Point, String>;
+    fn add_into(&self, acc: &mut Point, rhs: Point) -> Result<(), String>;
+    fn mul(&self, scalar: Num, point: Point) -> Result<Point, String>;
+    fn mul_in_subgroup(&self, scalar: Num, point: Point, point_order: Num)
+        -> Result<Point, String>;
+    fn mul_ladder(&self, scalar: Num, point: Point) -> Result<Point, String>;
+    /// Left-to-right double-and-add that also records, per scalar bit,
+    /// whether the step added the base or only doubled.
+    fn mul_trace(&self, scalar: Num, point: Point) -> Result<(Point, Vec<String>), String>;
+    fn mul_double(&self, k0: Num, p0: Point, k1: Num, p1: Point) -> Result<Point, String>;
+    fn mul_wnaf(&self, scalar: Num, point: Point, window: u32) -> Result<Point, String>;
+    fn mul_many(&self, pairs: &[(Num, Point)]) -> Result<Vec<Point>, String>;
+    fn checked_sum(&self, pt0: Point, pt1: Point) -> Result<Point, String>;
+    fn checked_mul(&self, scalar: Num, point: Point) -> Result<Point, String>;
+    fn precompute_base(&self, generator: Point) -> Result<BaseTable, String>;
+    fn base_mul(&self, table: &BaseTable, k: Num) -> Result<Point, String>;
+}
+
+/// `2^i * generator` for every bit of `k.unsigned_abs()`, so `base_mul`
+/// takes any `Num` scalar.
+#[derive(Debug, Clone)]
+pub struct BaseTable {
+    generator: Point,
+    doubles: Vec<ProjectivePoint>,
+}
+
+impl BaseTable {
+    pub fn generator(&self) -> Point {
+        self.generator
+    }
 }
 
+#[derive(Debug)]
 pub struct EllipticCurve {
-    pub coef1: Num,
-    pub coef0: Num,
-    pub prime: Num,
+    coef1: Num,
+    coef0: Num,
+    prime: Num,
+    residues: OnceCell<Vec<bool>>,
+    order: OnceCell<Num>,
+    order_factors: OnceCell<Vec<(Num, Num)>>,
+}
+
+#[cfg(test)]
+thread_local! {
+    static RESIDUE_SET_BUILDS: core::cell::Cell<usize> = const { core::cell::Cell::new(0) };
+}
+
+impl PartialEq for EllipticCurve {
+    fn eq(&self, other: &Self) -> bool {
+        (self.coef1, self.coef0, self.prime) == (other.coef1, other.coef0, other.prime)
+    }
+}
+impl Eq for EllipticCurve {}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EllipticCurveBuilder {
+    a: Option<Num>,
+    b: Option<Num>,
+    prime: Option<Num>,
+}
+
+impl EllipticCurveBuilder {
+    pub fn a(mut self, a: Num) -> Self {
+        self.a = Some(a);
+        self
+    }
+    pub fn b(mut self, b: Num) -> Self {
+        self.b = Some(b);
+        self
+    }
+    pub fn prime(mut self, prime: Num) -> Self {
+        self.prime = Some(prime);
+        self
+    }
+    pub fn build(self) -> Result<EllipticCurve, String> {
+        let (coef1, coef0, prime) = match (self.a, self.b, self.prime) {
+            (Some(a), Some(b), Some(prime)) => (a, b, prime),
+            _ => return Err(MISSING_FIELD.to_string()),
+        };
+        if !prime.is_prime()? {
+            return Err(NOT_A_PRIME.to_string());
+        }
+        if prime <= 3 {
+            return Err(SMALL_CHARACTERISTIC.to_string());
+        }
+        let (coef1, coef0) = EllipticCurve::reduce_coefficients(coef1, coef0, prime)?;
+        Ok(EllipticCurve {
+            coef1,
+            coef0,
+            prime,
+            residues: OnceCell::new(),
+            order: OnceCell::new(),
+            order_factors: OnceCell::new(),
+        })
+    }
 }
 
 impl EllipticCurve {
+    /// Short Weierstrass form only: the addition formulas divide by 2 and 3,
+    /// so `prime` must be greater than 3.
     pub fn new(coef1: Num, coef0: Num, prime: Num) -> Result<Self, String> {
+        EllipticCurve::builder()
+            .a(coef1)
+            .b(coef0)
+            .prime(prime)
+            .build()
+    }
+    /// Like `new`, but also rejects curves whose discriminant vanishes mod
+    /// `prime`, reporting the integer discriminant that `prime` divides.
+    pub fn new_checked(coef1: Num, coef0: Num, prime: Num) -> Result<Self, CurveError> {
+        let (a, b) = (coef1 as i128, coef0 as i128);
+        let disc = -16 * (4 * a * a * a + 27 * b * b);
+        let curve = match EllipticCurve::new(coef1, coef0, prime) {
+            Err(err) if err == NOT_AN_NON_SINGULAR => return Err(CurveError::Singular(disc)),
+            res => res.map_err(CurveError::Field)?,
+        };
+        if curve.discriminant().map_err(CurveError::Field)? == 0 {
+            return Err(CurveError::Singular(disc));
+        }
+        Ok(curve)
+    }
+    pub fn builder() -> EllipticCurveBuilder {
+        EllipticCurveBuilder::default()
+    }
+    pub fn coef1(&self) -> Num {
+        self.coef1
+    }
+    pub fn coef0(&self) -> Num {
+        self.coef0
+    }
+    pub fn prime(&self) -> Num {
+        self.prime
+    }
+    pub fn set_coef1(&mut self, coef1: Num) -> Result<(), String> {
+        self.set_coefficients(coef1, self.coef0)
+    }
+    pub fn set_coef0(&mut self, coef0: Num) -> Result<(), String> {
+        self.set_coefficients(self.coef1, coef0)
+    }
+    /// `prime` is fixed, so the residue cache stays valid across this; the
+    /// cached order and its factors do not.
+    fn set_coefficients(&mut self, coef1: Num, coef0: Num) -> Result<(), String> {
+        (self.coef1, self.coef0) = EllipticCurve::reduce_coefficients(coef1, coef0, self.prime)?;
+        self.order = OnceCell::new();
+        self.order_factors = OnceCell::new();
+        Ok(())
+    }
+    /// Shared by `build` and the setters: `y^2 = x^3` is rejected once the
+    /// coefficients are reduced, not only when they are literally zero.
+    fn reduce_coefficients(coef1: Num, coef0: Num, prime: Num) -> Result<(Num, Num), String> {
+        let (coef1, coef0) = (coef1.rem_euclid(prime), coef0.rem_euclid(prime));
+        if coef1 == 0 && coef0 == 0 {
+            return Err(NOT_AN_NON_SINGULAR.to_string());
+        }
+        Ok((coef1, coef0))
+    }
+    pub fn from_j_invariant(j: Num, prime: Num) -> Result<Self, String> {
         if !prime.is_prime()? {
             return Err(NOT_A_PRIME.to_string());
         }
-        if coef1 == 0 && coef0 == 0 {
+        let j = j.rem_euclid(prime);
+        if j == 0 {
+            return EllipticCurve::new(0, 1, prime);
+        }
+        if j == Num::rem_euclid(1728, prime) {
+            return EllipticCurve::new(1, 0, prime);
+        }
+        let k = mul_mod(j, (1728 - j).mod_inverse(prime)?, prime);
+        EllipticCurve::new(mul_mod(3, k, prime), mul_mod(2, k, prime), prime)
+    }
+    #[cfg(feature = "rng")]
+    pub fn random<R: Rng>(prime: Num, rng: &mut R) -> Result<Self, String> {
+        if !prime.is_prime()? {
+            return Err(NOT_A_PRIME.to_string());
+        }
+        for _ in 0..64 {
+            let (a, b) = (rng.below(prime), rng.below(prime));
+            if a == 0 && b == 0 {
+                continue;
+            }
+            let curve = EllipticCurve::new(a, b, prime)?;
+            if curve.discriminant()? != 0 {
+                return Ok(curve);
+            }
+        }
+        Err(NO_CURVE_FOUND.to_string())
+    }
+    pub fn discriminant(&self) -> Result<Num, String> {
+        let p = self.prime;
+        let sum = add_mod(
+            mul_mod(4, self.coef1.mod_pow(3, p)?, p),
+            mul_mod(27, self.coef0.mod_pow(2, p)?, p),
+            p,
+        );
+        Ok(mul_mod(-16, sum, p))
+    }
+    pub fn j_invariant(&self) -> Result<Num, String> {
+        let p = self.prime;
+        let disc = self.discriminant()?;
+        if disc == 0 {
             return Err(NOT_AN_NON_SINGULAR.to_string());
         }
-        Ok(EllipticCurve {
-            coef1: coef1.rem_euclid(prime),
-            coef0: coef0.rem_euclid(prime),
-            prime,
+        let num = mul_mod(-1728 * 64, self.coef1.mod_pow(3, p)?, p);
+        Ok(mul_mod(num, disc.mod_inverse(p)?, p))
+    }
+    pub fn valid_coordinates_range(&self) -> core::ops::Range<Num> {
+        0..self.prime
+    }
+    /// Bytes in one big-endian coordinate, `ceil(bits(prime) / 8)`.
+    pub fn point_byte_len(&self) -> usize {
+        (Num::BITS - self.prime.leading_zeros()).div_ceil(8) as usize
+    }
+    /// SEC1 uncompressed form: `0x04 || x || y`, each coordinate big-endian in
+    /// `point_byte_len` bytes; the point at infinity is the single byte `0x00`.
+    pub fn encode_uncompressed(&self, point: Point) -> Result<Vec<u8>, String> {
+        let len = self.point_byte_len();
+        Ok(match self.represent(point)? {
+            Point::Inf => vec![0x00],
+            Point::Affine(x, y) => [
+                &[0x04],
+                &x.to_be_bytes()[4 - len..],
+                &y.to_be_bytes()[4 - len..],
+            ]
+            .concat(),
+        })
+    }
+    pub fn decode_uncompressed(&self, bytes: &[u8]) -> Result<Point, String> {
+        let len = self.point_byte_len();
+        let coordinate = |chunk: &[u8]| chunk.iter().fold(0, |acc, b| acc << 8 | *b as Num);
+        match bytes {
+            [0x00] => Ok(Point::Inf),
+            [0x04, rest @ ..] if rest.len() == 2 * len => {
+                let (x, y) = rest.split_at(len);
+                self.affine_strict(coordinate(x), coordinate(y))
+            }
+            _ => Err(INVALID_ENCODING.to_string()),
+        }
+    }
+    /// Like `affine`, but rejects coordinates outside `0..prime` instead of
+    /// reducing them.
+    pub fn affine_strict(&self, x: Num, y: Num) -> Result<Point, String> {
+        let range = self.valid_coordinates_range();
+        if !range.contains(&x) || !range.contains(&y) {
+            return Err(OUT_OF_RANGE.to_string());
+        }
+        self.affine(x, y)
+    }
+    /// Maps `infinity_sentinel` to `Point::Inf` before validating, which takes
+    /// precedence even when the sentinel is itself an affine point.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn from_raw(
+        &self,
+        x: Num,
+        y: Num,
+        infinity_sentinel: Option<(Num, Num)>,
+    ) -> Result<Point, String> {
+        let point = self.normalize(Point::Affine(x, y));
+        match infinity_sentinel {
+            Some((sx, sy)) if point == self.normalize(Point::Affine(sx, sy)) => Ok(Point::Inf),
+            _ => Ok(self.represent(point)?),
+        }
+    }
+    /// Skips non-residues through the residue sieve if an earlier scan built
+    /// it; one `x` alone is not worth the `O(p)` build.
+    pub fn points_with_x(&self, x: Num) -> Result<Vec<Point>, String> {
+        let x = x.rem_euclid(self.prime);
+        let rhs = self.rhs(x)?;
+        if let Some(sieve) = self.residues.get() {
+            if rhs != 0 && !sieve[rhs as usize] {
+                return Ok(vec![]);
+            }
+        }
+        Ok(rhs
+            .sqrts_mod_prime(self.prime)?
+            .into_iter()
+            .map(|y| Point::Affine(x, y))
+            .collect())
+    }
+    /// Finds `u` with `target = (u^4 * a, u^6 * b)`, so that
+    /// `(x, y) -> (u^2 * x, u^3 * y)` maps this curve onto `target`.
+    pub fn isomorphism(&self, target: &EllipticCurve) -> Result<Option<Num>, String> {
+        if self.prime != target.prime {
+            return Err(DIFFERENT_FIELDS.to_string());
+        }
+        let p = self.prime;
+        for u in 1..p {
+            let u4 = u.mod_pow(4, p)?;
+            let u6 = mul_mod(u4, u.mod_pow(2, p)?, p);
+            if mul_mod(u4, self.coef1, p) == target.coef1
+                && mul_mod(u6, self.coef0, p) == target.coef0
+            {
+                return Ok(Some(u));
+            }
+        }
+        Ok(None)
+    }
+    pub fn map_point(&self, u: Num, point: Point) -> Result<Point, String> {
+        let p = self.prime;
+        if u.rem_euclid(p) == 0 {
+            return Err(NOT_INVERTIBLE.to_string());
+        }
+        Ok(match self.represent(point)? {
+            Point::Inf => Point::Inf,
+            Point::Affine(x, y) => Point::Affine(
+                mul_mod(u.mod_pow(2, p)?, x, p),
+                mul_mod(u.mod_pow(3, p)?, y, p),
+            ),
         })
     }
+    pub fn to_sage(&self) -> String {
+        format!(
+            "EllipticCurve(GF({}), [{}, {}])",
+            self.prime, self.coef1, self.coef0
+        )
+    }
+    pub fn to_pari(&self) -> String {
+        format!("ellinit([{}, {}], {})", self.coef1, self.coef0, self.prime)
+    }
+    /// `Display` with each coefficient shown as its representative in
+    /// `(-p/2, p/2]`, so `new(-1, 0, 47)` prints `-1` rather than `46`.
+    pub fn to_string_signed(&self) -> String {
+        let signed = |v: Num| {
+            if v > self.prime / 2 {
+                v - self.prime
+            } else {
+                v
+            }
+        };
+        format!(
+            "y^2 = x^3 + {} * x + {} (mod {})",
+            signed(self.coef1),
+            signed(self.coef0),
+            self.prime
+        )
+    }
+    /// `k mod subgroup_order` in `0..subgroup_order`; `NOT_A_POS` unless the
+    /// order is positive, as in `mul_in_subgroup`.
+    pub fn reduce_scalar(&self, k: Num, subgroup_order: Num) -> Result<Num, String> {
+        if subgroup_order < 1 {
+            return Err(NOT_A_POS.to_string());
+        }
+        Ok(k.rem_euclid(subgroup_order))
+    }
+    /// Preferred over building `Point::Affine` by hand: the coordinates are
+    /// reduced into `0..prime` and checked to lie on the curve.
+    pub fn affine(&self, x: Num, y: Num) -> Result<Point, String> {
+        Ok(self.represent(Point::Affine(x, y))?)
+    }
+    pub fn quadratic_twist(&self) -> Result<Self, String> {
+        let p = self.prime;
+        let d = Gf2::non_residue(p)?;
+        let dd = mul_mod(d, d, p);
+        EllipticCurve::new(
+            mul_mod(self.coef1, dd, p),
+            mul_mod(mul_mod(self.coef0, dd, p), d, p),
+            p,
+        )
+    }
+}
+impl EllipticCurve {
+    /// `residues[v]` tells whether `v` is a square mod `prime`; the sieve is
+    /// built on first use and shared by every later call.
+    pub(crate) fn residue_set(&self) -> Result<&[bool], String> {
+        if let Some(residues) = self.residues.get() {
+            return Ok(residues);
+        }
+        let sieve = self.prime.qr_sieve()?;
+        #[cfg(test)]
+        RESIDUE_SET_BUILDS.with(|n| n.set(n.get() + 1));
+        Ok(self.residues.get_or_init(|| sieve))
+    }
+    /// Filled by `Group::order` on first success and reset by the setters.
+    pub(crate) fn cached_order(&self) -> &OnceCell<Num> {
+        &self.order
+    }
+    /// Filled by `Group::order_factors` on first success and reset by the setters.
+    pub(crate) fn cached_order_factors(&self) -> &OnceCell<Vec<(Num, Num)>> {
+        &self.order_factors
+    }
+    /// Affine addition of points already reduced by `represent`; nothing is
+    /// re-validated, including the result.
+    fn sum_unchecked(&self, pt0: Point, pt1: Point) -> Result<Point, String> {
+        match (pt0, pt1) {
+            (Point::Inf, _) => Ok(pt1),
+            (_, Point::Inf) => Ok(pt0),
+            (Point::Affine(x0, y0), Point::Affine(x1, y1)) => {
+                let p = self.prime;
+                if x0 != x1 {
+                    let diff = mul_mod(y1 - y0, (x1 - x0).mod_pow(p - 2, p)?, p);
+                    let x2 = add_mod(diff.mod_pow(2, p)? - x0, -x1, p);
+                    let y2 = add_mod(mul_mod(diff, x2 - x0, p), y0, p);
+                    return Ok(self.normalize(Point::Affine(x2, -y2)));
+                }
+                // P + (-P) always lands here since -P shares its x-coordinate,
+                // so no separate inverse check is needed up front.
+                if add_mod(y0, y1, p) == 0 {
+                    return Ok(Point::Inf);
+                }
+                let num = add_mod(mul_mod(3, x0.mod_pow(2, p)?, p), self.coef1, p);
+                let diff = mul_mod(num, mul_mod(2, y0, p).mod_pow(p - 2, p)?, p);
+                let x2 = (diff.mod_pow(2, p)? - mul_mod(2, x0, p)).rem_euclid(p);
+                let y2 = add_mod(mul_mod(diff, x2 - x0, p), y0, p);
+                Ok(self.normalize(Point::Affine(x2, -y2)))
+            }
+        }
+    }
+    fn to_projective(&self, point: Point) -> ProjectivePoint {
+        match self.normalize(point) {
+            Point::Inf => ProjectivePoint::INF,
+            Point::Affine(x, y) => ProjectivePoint { x, y, z: 1 },
+        }
+    }
+    fn to_affine(&self, point: ProjectivePoint) -> Result<Point, String> {
+        if point.z == 0 {
+            return Ok(Point::Inf);
+        }
+        let z_inv = point.z.mod_inverse(self.prime)?;
+        Ok(Point::Affine(
+            mul_mod(point.x, z_inv, self.prime),
+            mul_mod(point.y, z_inv, self.prime),
+        ))
+    }
+    fn proj_double(&self, pt: ProjectivePoint) -> ProjectivePoint {
+        let p = self.prime;
+        let m = |a: Num, b: Num| mul_mod(a, b, p);
+        if pt.z == 0 || pt.y == 0 {
+            return ProjectivePoint::INF;
+        }
+        let w = add_mod(m(self.coef1, m(pt.z, pt.z)), m(3, m(pt.x, pt.x)), p);
+        let s = m(pt.y, pt.z);
+        let b = m(m(pt.x, pt.y), s);
+        let h = (m(w, w) - m(8, b)).rem_euclid(p);
+        ProjectivePoint {
+            x: m(m(2, h), s),
+            y: (m(w, (m(4, b) - h).rem_euclid(p)) - m(8, m(m(pt.y, pt.y), m(s, s)))).rem_euclid(p),
+            z: m(8, m(s, m(s, s))),
+        }
+    }
+    fn proj_add(&self, pt0: ProjectivePoint, pt1: ProjectivePoint) -> ProjectivePoint {
+        let p = self.prime;
+        let m = |a: Num, b: Num| mul_mod(a, b, p);
+        if pt0.z == 0 {
+            return pt1;
+        }
+        if pt1.z == 0 {
+            return pt0;
+        }
+        let u = (m(pt1.y, pt0.z) - m(pt0.y, pt1.z)).rem_euclid(p);
+        let v = (m(pt1.x, pt0.z) - m(pt0.x, pt1.z)).rem_euclid(p);
+        if v == 0 {
+            return match u {
+                0 => self.proj_double(pt0),
+                _ => ProjectivePoint::INF,
+            };
+        }
+        let vv = m(v, v);
+        let vvv = m(v, vv);
+        let zz = m(pt0.z, pt1.z);
+        let r = m(vv, m(pt0.x, pt1.z));
+        let w = ((m(m(u, u), zz) - vvv).rem_euclid(p) - m(2, r)).rem_euclid(p);
+        ProjectivePoint {
+            x: m(v, w),
+            y: (m(u, (r - w).rem_euclid(p)) - m(vvv, m(pt0.y, pt1.z))).rem_euclid(p),
+            z: m(vvv, zz),
+        }
+    }
+    fn mul_projective(&self, scalar: Num, point: Point) -> Result<Point, String> {
+        if !self.is_on(point) {
+            return Err(NOT_ON_THE_CURVE.to_string());
+        }
+        let base = match scalar < 0 {
+            true => self.to_projective(point).neg(self.prime),
+            false => self.to_projective(point),
+        };
+        self.to_affine(self.proj_mul(scalar.unsigned_abs(), base))
+    }
+    fn proj_mul(&self, scalar: u32, base: ProjectivePoint) -> ProjectivePoint {
+        let mut acc = ProjectivePoint::INF;
+        for i in (0..u32::BITS - scalar.leading_zeros()).rev() {
+            acc = self.proj_double(acc);
+            if (scalar >> i) & 1 == 1 {
+                acc = self.proj_add(acc, base);
+            }
+        }
+        acc
+    }
 }
-impl std::fmt::Display for EllipticCurve {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+/// `{:#}` appends `[#E=.., j=.., Δ=..]`, with `?` for anything that fails
+/// to compute (e.g. `j` of a singular curve).
+impl core::fmt::Display for EllipticCurve {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         write!(
             f,
             "y^2 = x^3 + {} * x + {} (mod {})",
             self.coef1, self.coef0, self.prime
-        )
+        )?;
+        if f.alternate() {
+            let show = |value: Result<Num, String>| match value {
+                Ok(v) => v.to_string(),
+                Err(_) => "?".to_string(),
+            };
+            write!(
+                f,
+                " [#E={}, j={}, Δ={}]",
+                show(self.order()),
+                show(self.j_invariant()),
+                show(self.discriminant())
+            )?;
+        }
+        Ok(())
     }
 }
 impl Op for EllipticCurve {
@@ -70,27 +650,69 @@ impl Op for EllipticCurve {
         Ok(y.mod_pow(2, self.prime)?)
     }
     fn rhs(&self, x: Num) -> Result<Num, String> {
-        Ok(
-            (x.mod_pow(3, self.prime)? + (self.coef1 * x).rem_euclid(self.prime) + self.coef0)
-                .rem_euclid(self.prime),
-        )
+        let p = self.prime;
+        Ok(add_mod(
+            add_mod(x.mod_pow(3, p)?, mul_mod(self.coef1, x, p), p),
+            self.coef0,
+            p,
+        ))
     }
     fn is_on(&self, point: Point) -> bool {
         match point {
             Point::Inf => true,
-            Point::Affine(x, y) => self.lhs(y) == self.rhs(x),
+            Point::Affine(x, y) => self.is_on_affine(x, y),
         }
     }
-    fn represent(&self, point: Point) -> Result<Point, &str> {
-        if !self.is_on(point) {
-            return Err(NOT_ON_THE_CURVE);
+    fn is_on_affine(&self, x: Num, y: Num) -> bool {
+        match (
+            self.lhs(y.rem_euclid(self.prime)),
+            self.rhs(x.rem_euclid(self.prime)),
+        ) {
+            (Ok(lhs), Ok(rhs)) => lhs == rhs,
+            _ => false,
         }
-        Ok(match point {
+    }
+    fn is_on_projective(&self, x: Num, y: Num, z: Num) -> bool {
+        let p = self.prime;
+        let m = |a: Num, b: Num| mul_mod(a, b, p);
+        let (x, y, z) = (x.rem_euclid(p), y.rem_euclid(p), z.rem_euclid(p));
+        if z == 0 {
+            return true;
+        }
+        let zz = m(z, z);
+        let lhs = m(m(y, y), z);
+        let rhs = add_mod(
+            add_mod(m(m(x, x), x), m(self.coef1, m(x, zz)), p),
+            m(self.coef0, m(z, zz)),
+            p,
+        );
+        lhs == rhs
+    }
+    fn normalize(&self, point: Point) -> Point {
+        match point {
             Point::Inf => Point::Inf,
             Point::Affine(x, y) => {
                 Point::Affine(x.rem_euclid(self.prime), y.rem_euclid(self.prime))
             }
-        })
+        }
+    }
+    fn represent(&self, point: Point) -> Result<Point, &str> {
+        let point = self.normalize(point);
+        if !self.is_on(point) {
+            return Err(NOT_ON_THE_CURVE);
+        }
+        Ok(point)
+    }
+    fn checked_represent(&self, point: Point) -> Result<Point, CurveError> {
+        let point = self.normalize(point);
+        if let Point::Affine(x, y) = point {
+            let lhs = self.lhs(y).map_err(CurveError::Field)?;
+            let rhs = self.rhs(x).map_err(CurveError::Field)?;
+            if lhs != rhs {
+                return Err(CurveError::NotOnCurve);
+            }
+        }
+        Ok(point)
     }
     fn inv(&self, point: Point) -> Result<Point, &str> {
         if !self.is_on(point) {
@@ -102,77 +724,995 @@ impl Op for EllipticCurve {
         })
     }
     fn sum(&self, pt0: Point, pt1: Point) -> Result<Point, String> {
+        self.sum_unchecked(self.represent(pt0)?, self.represent(pt1)?)
+    }
+    fn sum_explain(&self, pt0: Point, pt1: Point) -> Result<(Point, &'static str), String> {
+        let label = match (self.represent(pt0)?, self.represent(pt1)?) {
+            (Point::Inf, _) | (_, Point::Inf) => "identity",
+            (Point::Affine(x0, y0), Point::Affine(x1, y1)) if x0 == x1 => {
+                match add_mod(y0, y1, self.prime) {
+                    0 => "inverse->Inf",
+                    _ => "doubling",
+                }
+            }
+            _ => "generic",
+        };
+        Ok((self.sum(pt0, pt1)?, label))
+    }
+    fn sum_all(&self, points: &[Point]) -> Result<Point, String> {
+        points
+            .iter()
+            .try_fold(Point::Inf, |acc, pt| self.sum(acc, *pt))
+    }
+    fn sum_chain(&self, points: &[Point]) -> Result<Point, String> {
+        let points = points
+            .iter()
+            .map(|pt| self.represent(*pt))
+            .collect::<Result<Vec<Point>, &str>>()?;
+        points
+            .into_iter()
+            .try_fold(Point::Inf, |acc, pt| self.sum_unchecked(acc, pt))
+    }
+    fn add_into(&self, acc: &mut Point, rhs: Point) -> Result<(), String> {
+        *acc = self.sum(*acc, rhs)?;
+        Ok(())
+    }
+    fn mul(&self, scalar: Num, point: Point) -> Result<Point, String> {
+        self.mul_projective(scalar, point)
+    }
+    fn mul_in_subgroup(
+        &self,
+        scalar: Num,
+        point: Point,
+        point_order: Num,
+    ) -> Result<Point, String> {
+        if point_order < 1 {
+            return Err(NOT_A_POS.to_string());
+        }
+        if !self.mul(point_order, point)?.is_inf() {
+            return Err(WRONG_ORDER.to_string());
+        }
+        self.mul(scalar.rem_euclid(point_order), point)
+    }
+    fn mul_ladder(&self, scalar: Num, point: Point) -> Result<Point, String> {
+        if !self.is_on(point) {
+            return Err(NOT_ON_THE_CURVE.to_string());
+        }
+        let mut r0 = ProjectivePoint::INF;
+        let mut r1 = match scalar < 0 {
+            true => self.to_projective(point).neg(self.prime),
+            false => self.to_projective(point),
+        };
+        let scalar = scalar.unsigned_abs();
+        for i in (0..u32::BITS).rev() {
+            let sum = self.proj_add(r0, r1);
+            if (scalar >> i) & 1 == 1 {
+                r0 = sum;
+                r1 = self.proj_double(r1);
+            } else {
+                r1 = sum;
+                r0 = self.proj_double(r0);
+            }
+        }
+        self.to_affine(r0)
+    }
+    fn mul_trace(&self, scalar: Num, point: Point) -> Result<(Point, Vec<String>), String> {
+        let point = match scalar < 0 {
+            true => self.inv(point)?,
+            false => self.represent(point)?,
+        };
+        let scalar = scalar.unsigned_abs();
+        let mut acc = Point::Inf;
+        let mut log = Vec::new();
+        for i in (0..u32::BITS - scalar.leading_zeros()).rev() {
+            acc = self.sum(acc, acc)?;
+            if (scalar >> i) & 1 == 1 {
+                acc = self.sum(acc, point)?;
+                log.push("bit=1: add".to_string());
+            } else {
+                log.push("bit=0: double only".to_string());
+            }
+        }
+        Ok((acc, log))
+    }
+    fn mul_double(&self, k0: Num, p0: Point, k1: Num, p1: Point) -> Result<Point, String> {
+        if !self.is_on(p0) | !self.is_on(p1) {
+            return Err(NOT_ON_THE_CURVE.to_string());
+        }
+        let signed = |k: Num, pt: Point| match k < 0 {
+            true => self.to_projective(pt).neg(self.prime),
+            false => self.to_projective(pt),
+        };
+        let (q0, q1) = (signed(k0, p0), signed(k1, p1));
+        let (k0, k1) = (k0.unsigned_abs(), k1.unsigned_abs());
+        let q01 = self.proj_add(q0, q1);
+        let mut acc = ProjectivePoint::INF;
+        for i in (0..u32::BITS - (k0 | k1).leading_zeros()).rev() {
+            acc = self.proj_double(acc);
+            acc = match ((k0 >> i) & 1, (k1 >> i) & 1) {
+                (1, 1) => self.proj_add(acc, q01),
+                (1, _) => self.proj_add(acc, q0),
+                (_, 1) => self.proj_add(acc, q1),
+                _ => acc,
+            };
+        }
+        self.to_affine(acc)
+    }
+    fn mul_wnaf(&self, scalar: Num, point: Point, window: u32) -> Result<Point, String> {
+        if !(2..=6).contains(&window) {
+            return Err(INVALID_WINDOW.to_string());
+        }
+        if scalar < 1 << window {
+            return self.mul(scalar, point);
+        }
+        if !self.is_on(point) {
+            return Err(NOT_ON_THE_CURVE.to_string());
+        }
+        let base = self.to_projective(point);
+        let dbl = self.proj_double(base);
+        let mut table = vec![base];
+        for i in 1..1 << (window - 2) {
+            table.push(self.proj_add(table[i - 1], dbl));
+        }
+        let mut naf = Vec::new();
+        let mut k = scalar as i64;
+        while k > 0 {
+            let mut d = 0;
+            if k & 1 == 1 {
+                d = k & ((1 << window) - 1);
+                if d >= 1 << (window - 1) {
+                    d -= 1 << window;
+                }
+                k -= d;
+            }
+            naf.push(d as Num);
+            k >>= 1;
+        }
+        let mut acc = ProjectivePoint::INF;
+        for d in naf.into_iter().rev() {
+            acc = self.proj_double(acc);
+            if d > 0 {
+                acc = self.proj_add(acc, table[(d / 2) as usize]);
+            } else if d < 0 {
+                acc = self.proj_add(acc, table[(-d / 2) as usize].neg(self.prime));
+            }
+        }
+        self.to_affine(acc)
+    }
+    fn mul_many(&self, pairs: &[(Num, Point)]) -> Result<Vec<Point>, String> {
+        let mut results = Vec::with_capacity(pairs.len());
+        for &(scalar, point) in pairs {
+            if !self.is_on(point) {
+                return Err(NOT_ON_THE_CURVE.to_string());
+            }
+            let base = match scalar < 0 {
+                true => self.to_projective(point).neg(self.prime),
+                false => self.to_projective(point),
+            };
+            results.push(self.proj_mul(scalar.unsigned_abs(), base));
+        }
+        let zs: Vec<Num> = results
+            .iter()
+            .filter(|pt| pt.z != 0)
+            .map(|pt| pt.z)
+            .collect();
+        let mut z_invs = batch_inverse(&zs, self.prime)?.into_iter();
+        Ok(results
+            .into_iter()
+            .map(|pt| match pt.z {
+                0 => Point::Inf,
+                _ => {
+                    let z_inv = z_invs.next().unwrap();
+                    Point::Affine(
+                        mul_mod(pt.x, z_inv, self.prime),
+                        mul_mod(pt.y, z_inv, self.prime),
+                    )
+                }
+            })
+            .collect())
+    }
+    fn checked_sum(&self, pt0: Point, pt1: Point) -> Result<Point, String> {
         if !self.is_on(pt0) | !self.is_on(pt1) {
             return Err(NOT_ON_THE_CURVE.to_string());
         }
-        match (pt0, pt1) {
-            (Point::Inf, _) => Ok(self.represent(pt1)?),
-            (_, Point::Inf) => Ok(self.represent(pt0)?),
+        let p = self.prime;
+        let add = |a: Num, b: Num| {
+            a.checked_add(b)
+                .map(|c| c.rem_euclid(p))
+                .ok_or(OVERFLOW.to_string())
+        };
+        let mul = |a: Num, b: Num| {
+            a.checked_mul(b)
+                .map(|c| c.rem_euclid(p))
+                .ok_or(OVERFLOW.to_string())
+        };
+        match (self.normalize(pt0), self.normalize(pt1)) {
+            (Point::Inf, pt) | (pt, Point::Inf) => Ok(pt),
             (Point::Affine(x0, y0), Point::Affine(x1, y1)) => {
-                if (x0 - x1).rem_euclid(self.prime) != 0 {
-                    let diff = ((y1 - y0) * (x1 - x0).mod_pow(self.prime - 2, self.prime)?)
-                        .rem_euclid(self.prime);
-                    let x2 = diff.mod_pow(2, self.prime)? - x0 - x1;
-                    let y2 = diff * (x2 - x0) + y0;
-                    return Ok(self.inv(Point::Affine(x2, y2))?);
-                }
-                if (y0 + y1).rem_euclid(self.prime) == 0 {
+                let diff = if x0 != x1 {
+                    mul((y1 - y0).rem_euclid(p), (x1 - x0).mod_inverse(p)?)?
+                } else if add(y0, y1)? == 0 {
                     return Ok(Point::Inf);
-                }
-                let diff = ((3 * x0.mod_pow(2, self.prime)? + self.coef1)
-                    * (2 * y0).mod_pow(self.prime - 2, self.prime)?)
-                .rem_euclid(self.prime);
-                let x2 = diff.mod_pow(2, self.prime)? - (2 * x0).rem_euclid(self.prime);
-                let y2 = (diff * (x2 - x0)).rem_euclid(self.prime) + y0;
-                Ok(self.inv(Point::Affine(x2, y2))?)
+                } else {
+                    let num = add(mul(3, mul(x0, x0)?)?, self.coef1)?;
+                    mul(num, add(y0, y0)?.mod_inverse(p)?)?
+                };
+                let x2 = ((mul(diff, diff)? - x0).rem_euclid(p) - x1).rem_euclid(p);
+                let y2 = (mul(diff, (x0 - x2).rem_euclid(p))? - y0).rem_euclid(p);
+                Ok(Point::Affine(x2, y2))
+            }
+        }
+    }
+    fn checked_mul(&self, scalar: Num, point: Point) -> Result<Point, String> {
+        if !self.is_on(point) {
+            return Err(NOT_ON_THE_CURVE.to_string());
+        }
+        let point = match scalar < 0 {
+            true => self.inv(point)?,
+            false => point,
+        };
+        let scalar = scalar.unsigned_abs();
+        let mut acc = Point::Inf;
+        for i in (0..u32::BITS - scalar.leading_zeros()).rev() {
+            acc = self.checked_sum(acc, acc)?;
+            if (scalar >> i) & 1 == 1 {
+                acc = self.checked_sum(acc, point)?;
+            }
+        }
+        Ok(acc)
+    }
+    fn precompute_base(&self, generator: Point) -> Result<BaseTable, String> {
+        let generator = self.represent(generator)?;
+        let mut doubles = vec![self.to_projective(generator)];
+        for i in 1..u32::BITS as usize {
+            doubles.push(self.proj_double(doubles[i - 1]));
+        }
+        Ok(BaseTable { generator, doubles })
+    }
+    fn base_mul(&self, table: &BaseTable, k: Num) -> Result<Point, String> {
+        let mut acc = ProjectivePoint::INF;
+        for (i, pt) in table.doubles.iter().enumerate() {
+            if (k.unsigned_abs() >> i) & 1 == 1 {
+                acc = self.proj_add(acc, *pt);
             }
         }
+        match k < 0 {
+            true => self.to_affine(acc.neg(self.prime)),
+            false => self.to_affine(acc),
+        }
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    #[cfg(not(feature = "rng"))]
+    use crate::rng::*;
 
-    mod test_op {
+    mod test_point {
         use super::*;
 
         #[test]
-        fn test_lhs() {
-            let curve = EllipticCurve::new(9, 20, 7).unwrap();
-            assert_eq!(4, curve.lhs(2).unwrap());
+        fn test_ord() {
+            let mut points = vec![
+                Point::Affine(3, 1),
+                Point::Affine(0, 5),
+                Point::Inf,
+                Point::Affine(3, 0),
+                Point::Affine(1, 9),
+            ];
+            points.sort();
+            assert_eq!(
+                vec![
+                    Point::Inf,
+                    Point::Affine(0, 5),
+                    Point::Affine(1, 9),
+                    Point::Affine(3, 0),
+                    Point::Affine(3, 1),
+                ],
+                points
+            );
         }
         #[test]
-        fn test_rhs() {
-            let curve = EllipticCurve::new(9, 20, 7).unwrap();
-            assert_eq!(4, curve.rhs(2).unwrap());
+        fn test_ct_eq() {
+            let mut points = vec![Point::Inf];
+            for x in -3..4 {
+                for y in -3..4 {
+                    points.push(Point::Affine(x, y));
+                }
+            }
+            points.push(Point::Affine(Num::MAX, Num::MIN));
+            for p0 in &points {
+                for p1 in &points {
+                    assert_eq!(p0 == p1, p0.ct_eq(p1));
+                }
+            }
+            assert!(!Point::Inf.ct_eq(&Point::Affine(0, 0)));
         }
         #[test]
-        fn test_is_on() {
-            let curve = EllipticCurve::new(7, 5, 13).unwrap();
-            assert!(curve.is_on(Point::Inf));
-            assert!(curve.is_on(Point::Affine(5, 10)));
-            assert!(curve.is_on(Point::Affine(9, 2)));
-            assert!(curve.is_on(Point::Affine(8, 1)));
-            assert!(curve.is_on(Point::Affine(8 + 13, 1 + 13)));
-            assert!(!curve.is_on(Point::Affine(9, 1)));
-
-            let curve = EllipticCurve::new(77, 42, 97).unwrap();
-            assert!(curve.is_on(Point::Inf));
-            assert!(curve.is_on(Point::Affine(22, 68)));
-            assert!(curve.is_on(Point::Affine(64, 48)));
-            assert!(!curve.is_on(Point::Affine(35, 54)));
-            assert!(!curve.is_on(Point::Affine(35, 65)));
-            assert!(!curve.is_on(Point::Affine(10, 10)));
+        fn test_coordinates() {
+            assert_eq!(None, Point::Inf.coordinates());
+            assert_eq!(None, Point::Inf.x());
+            assert_eq!(None, Point::Inf.y());
+            let pt = Point::Affine(38, 47);
+            assert_eq!(Some((38, 47)), pt.coordinates());
+            assert_eq!(Some(38), pt.x());
+            assert_eq!(Some(47), pt.y());
         }
+    }
+    mod test_curve {
+        use super::*;
+
         #[test]
-        fn test_represent() {
-            let curve = EllipticCurve::new(7, 5, 13).unwrap();
-            let pt = Point::Affine(3, 1);
-            assert_eq!(pt, curve.represent(Point::Affine(3 + 13, 1 + 13)).unwrap());
+        fn test_builder() {
+            let curve = EllipticCurve::builder()
+                .a(-1)
+                .b(0)
+                .prime(71)
+                .build()
+                .unwrap();
+            assert_eq!((70, 0, 71), (curve.coef1, curve.coef0, curve.prime));
+            let curve = EllipticCurve::builder()
+                .prime(13)
+                .b(5)
+                .a(7)
+                .build()
+                .unwrap();
+            assert_eq!((7, 5, 13), (curve.coef1, curve.coef0, curve.prime));
             assert_eq!(
-                pt,
-                curve
+                Err(MISSING_FIELD.to_string()),
+                EllipticCurve::builder().a(1).b(1).build().map(|_| ())
+            );
+            assert_eq!(
+                Err(MISSING_FIELD.to_string()),
+                EllipticCurve::builder().a(1).prime(13).build().map(|_| ())
+            );
+            assert_eq!(
+                Err(MISSING_FIELD.to_string()),
+                EllipticCurve::builder().build().map(|_| ())
+            );
+            assert_eq!(
+                Err(NOT_A_PRIME.to_string()),
+                EllipticCurve::builder()
+                    .a(1)
+                    .b(1)
+                    .prime(15)
+                    .build()
+                    .map(|_| ())
+            );
+            assert!(EllipticCurve::builder()
+                .a(1)
+                .b(1)
+                .prime(-7)
+                .build()
+                .is_err());
+            assert_eq!(
+                Err(NOT_AN_NON_SINGULAR.to_string()),
+                EllipticCurve::builder()
+                    .a(0)
+                    .b(0)
+                    .prime(13)
+                    .build()
+                    .map(|_| ())
+            );
+            assert_eq!(
+                Err(NOT_AN_NON_SINGULAR.to_string()),
+                EllipticCurve::new(13, -26, 13).map(|_| ())
+            );
+        }
+
+        #[test]
+        fn test_trivial_group() {
+            // `new` rejects p = 3, but y^2 = x^3 + 2x + 2 over F_3 is the
+            // smallest curve whose group is just the identity.
+            let curve = EllipticCurve {
+                coef1: 2,
+                coef0: 2,
+                prime: 3,
+                residues: OnceCell::new(),
+                order: OnceCell::new(),
+                order_factors: OnceCell::new(),
+            };
+            assert_eq!(1, curve.order().unwrap());
+            assert_eq!(vec![Point::Inf], curve.solutions().unwrap());
+            assert_eq!((1, 1), curve.decomposition().unwrap());
+            assert!(curve.is_cyclic().unwrap());
+            assert_eq!(Some(Point::Inf), curve.find_generator().unwrap());
+            assert_eq!(vec![Point::Inf], curve.generators().unwrap());
+            assert_eq!(Some(Point::Inf), curve.generator_of_order(1).unwrap());
+        }
+        #[test]
+        fn test_small_characteristic() {
+            for (a, b) in [(1, 1), (0, 1), (1, 0)] {
+                for prime in [2, 3] {
+                    assert_eq!(
+                        Err(SMALL_CHARACTERISTIC.to_string()),
+                        EllipticCurve::new(a, b, prime).map(|_| ())
+                    );
+                }
+            }
+            assert!(EllipticCurve::new(1, 1, 5).is_ok());
+            assert!(EllipticCurve::from_j_invariant(0, 3).is_err());
+        }
+        #[cfg(feature = "rng")]
+        #[test]
+        fn test_random() {
+            let mut rng = XorShift::new(7);
+            assert!(EllipticCurve::random(12, &mut rng).is_err());
+            assert!(EllipticCurve::random(-5, &mut rng).is_err());
+            assert!(EllipticCurve::random(3, &mut rng).is_err());
+            for prime in [5, 7, 53, 71, 199] {
+                for _ in 0..10 {
+                    let curve = EllipticCurve::random(prime, &mut rng).unwrap();
+                    assert_eq!(
+                        curve,
+                        EllipticCurve::new(curve.coef1, curve.coef0, prime).unwrap()
+                    );
+                    assert_ne!(0, curve.discriminant().unwrap());
+                    let (lo, hi) = curve.hasse_interval().unwrap();
+                    assert!((lo..=hi).contains(&curve.order().unwrap()));
+                }
+            }
+        }
+        #[test]
+        fn test_discriminant() {
+            let curve = EllipticCurve::new(-1, 0, 71).unwrap();
+            assert_eq!(64, curve.discriminant().unwrap());
+            let curve = EllipticCurve::new(-3, 2, 71).unwrap();
+            assert_eq!(0, curve.discriminant().unwrap());
+            let curve = EllipticCurve::new(2, 3, 97).unwrap();
+            assert_eq!(62, curve.discriminant().unwrap());
+            let p = 2147390939;
+            // `4 * (-1)^3 = p - 4` and `27 * (-2)^2 = 108` sum past `Num::MAX`.
+            let curve = EllipticCurve::new(p - 1, p - 2, p).unwrap();
+            assert_eq!(p - 16 * 104, curve.discriminant().unwrap());
+            let curve = EllipticCurve::new(p - 3, 2, p).unwrap();
+            assert_eq!(0, curve.discriminant().unwrap());
+        }
+        #[test]
+        fn test_j_invariant() {
+            assert_eq!(
+                0,
+                EllipticCurve::new(0, 5, 71).unwrap().j_invariant().unwrap()
+            );
+            assert_eq!(
+                1728 % 71,
+                EllipticCurve::new(5, 0, 71).unwrap().j_invariant().unwrap()
+            );
+            assert!(EllipticCurve::new(-3, 2, 71)
+                .unwrap()
+                .j_invariant()
+                .is_err());
+        }
+        #[test]
+        fn test_from_j_invariant() {
+            assert!(EllipticCurve::from_j_invariant(5, 9).is_err());
+            for prime in [5, 7, 53, 71, 97] {
+                for j in 0..prime {
+                    let curve = EllipticCurve::from_j_invariant(j, prime).unwrap();
+                    assert_eq!(j, curve.j_invariant().unwrap());
+                }
+            }
+            for j in [2, 5, 1_000_000_006] {
+                let curve = EllipticCurve::from_j_invariant(j, 1_000_000_007).unwrap();
+                assert_eq!(j, curve.j_invariant().unwrap());
+            }
+            assert_eq!(
+                3,
+                EllipticCurve::from_j_invariant(3 + 71, 71)
+                    .unwrap()
+                    .j_invariant()
+                    .unwrap()
+            );
+        }
+        #[test]
+        fn test_eq() {
+            assert_eq!(
+                EllipticCurve::new(-1, 0, 47).unwrap(),
+                EllipticCurve::new(46, 0, 47).unwrap()
+            );
+            assert_eq!(
+                EllipticCurve::new(3 + 53, 11 - 53, 53).unwrap(),
+                EllipticCurve::new(3, 11, 53).unwrap()
+            );
+            assert_ne!(
+                EllipticCurve::new(-1, 0, 47).unwrap(),
+                EllipticCurve::new(-1, 0, 71).unwrap()
+            );
+            assert_ne!(
+                EllipticCurve::new(1, 0, 47).unwrap(),
+                EllipticCurve::new(1, 0, 71).unwrap()
+            );
+            assert_ne!(
+                EllipticCurve::new(-1, 0, 47).unwrap(),
+                EllipticCurve::new(0, -1, 47).unwrap()
+            );
+        }
+        #[test]
+        fn test_to_sage_pari() {
+            let curve = EllipticCurve::new(-1, 0, 47).unwrap();
+            assert_eq!("EllipticCurve(GF(47), [46, 0])", curve.to_sage());
+            assert_eq!("ellinit([46, 0], 47)", curve.to_pari());
+            let curve = EllipticCurve::new(2, 3, 97).unwrap();
+            assert_eq!("EllipticCurve(GF(97), [2, 3])", curve.to_sage());
+            assert_eq!("ellinit([2, 3], 97)", curve.to_pari());
+        }
+        #[test]
+        fn test_display_alternate() {
+            let curve = EllipticCurve::new(-1, 0, 47).unwrap();
+            assert_eq!("y^2 = x^3 + 46 * x + 0 (mod 47)", format!("{}", curve));
+            assert_eq!(
+                "y^2 = x^3 + 46 * x + 0 (mod 47) [#E=48, j=36, Δ=17]",
+                format!("{:#}", curve)
+            );
+            let curve = EllipticCurve::new(1, 1, 31).unwrap();
+            assert_eq!(
+                "y^2 = x^3 + 1 * x + 1 (mod 31) [#E=33, j=?, Δ=0]",
+                format!("{:#}", curve)
+            );
+        }
+        #[test]
+        fn test_to_string_signed() {
+            let curve = EllipticCurve::new(-1, 0, 47).unwrap();
+            assert_eq!("y^2 = x^3 + 46 * x + 0 (mod 47)", curve.to_string());
+            assert_eq!("y^2 = x^3 + -1 * x + 0 (mod 47)", curve.to_string_signed());
+            let curve = EllipticCurve::new(23, 24, 47).unwrap();
+            assert_eq!(
+                "y^2 = x^3 + 23 * x + -23 (mod 47)",
+                curve.to_string_signed()
+            );
+            let prime = 11;
+            for a in -prime..2 * prime {
+                let curve = EllipticCurve::new(a, 1, prime).unwrap();
+                let signed = curve.to_string_signed();
+                let coef: Num = signed.split(' ').nth(4).unwrap().parse().unwrap();
+                assert_eq!(a.rem_euclid(prime), coef.rem_euclid(prime));
+                let r = a.rem_euclid(prime);
+                assert_eq!(core::cmp::min(r, prime - r), coef.abs());
+            }
+        }
+        #[test]
+        fn test_reduce_scalar() {
+            let curve = EllipticCurve::new(3, 11, 53).unwrap();
+            assert_eq!(Ok(0), curve.reduce_scalar(19, 19));
+            assert_eq!(Ok(18), curve.reduce_scalar(-1, 19));
+            assert_eq!(Ok(5), curve.reduce_scalar(5, 19));
+            for n in [0, -1, -19, Num::MIN] {
+                assert_eq!(Err(NOT_A_POS.to_string()), curve.reduce_scalar(5, n));
+            }
+            for (gene, n) in [(Point::Affine(38, 47), 19), (Point::Affine(9, 5), 57)] {
+                for k in (-200..200).chain([Num::MAX, Num::MIN + 1]) {
+                    let reduced = curve.reduce_scalar(k, n).unwrap();
+                    assert!((0..n).contains(&reduced));
+                    if k >= 0 {
+                        assert_eq!(
+                            curve.mul(k, gene).unwrap(),
+                            curve.mul(reduced, gene).unwrap()
+                        );
+                    } else {
+                        let expected = curve.inv(curve.mul(-k, gene).unwrap()).unwrap();
+                        assert_eq!(expected, curve.mul(reduced, gene).unwrap());
+                    }
+                }
+            }
+        }
+        #[test]
+        fn test_affine() {
+            let curve = EllipticCurve::new(3, 11, 53).unwrap();
+            assert_eq!(Point::Affine(9, 5), curve.affine(9, 5).unwrap());
+            assert_eq!(Point::Affine(38, 47), curve.affine(38, 47).unwrap());
+            assert_eq!(Point::Affine(9, 5), curve.affine(9 + 53, 5 - 106).unwrap());
+            assert_eq!(Point::Affine(38, 47), curve.affine(-15, -6).unwrap());
+            assert!(curve.affine(9, 6).is_err());
+            assert!(curve.affine(9 + 53, 6).is_err());
+        }
+        #[test]
+        fn test_point_byte_len() {
+            for (prime, len) in [
+                (5, 1),
+                (47, 1),
+                (251, 1),
+                (257, 2),
+                (40009, 2),
+                (65521, 2),
+                (65537, 3),
+            ] {
+                assert_eq!(
+                    len,
+                    EllipticCurve::new(1, 1, prime).unwrap().point_byte_len()
+                );
+            }
+        }
+        #[test]
+        fn test_encode_uncompressed() {
+            let curve = EllipticCurve::new(3, 11, 53).unwrap();
+            assert_eq!(
+                vec![0x04, 38, 47],
+                curve.encode_uncompressed(Point::Affine(38, 47)).unwrap()
+            );
+            assert_eq!(vec![0x00], curve.encode_uncompressed(Point::Inf).unwrap());
+            assert!(curve.encode_uncompressed(Point::Affine(9, 6)).is_err());
+            for pt in curve.solutions().unwrap() {
+                let bytes = curve.encode_uncompressed(pt).unwrap();
+                assert_eq!(pt, curve.decode_uncompressed(&bytes).unwrap());
+            }
+            let curve = EllipticCurve::new(-1, 0, 40009).unwrap();
+            let pt = curve.points_with_x(40008).unwrap()[0];
+            let bytes = curve.encode_uncompressed(pt).unwrap();
+            assert_eq!(5, bytes.len());
+            assert_eq!([0x04, 0x9c, 0x48], bytes[..3]);
+            assert_eq!(pt, curve.decode_uncompressed(&bytes).unwrap());
+        }
+        #[test]
+        fn test_decode_uncompressed() {
+            let curve = EllipticCurve::new(3, 11, 53).unwrap();
+            assert_eq!(
+                Point::Affine(9, 5),
+                curve.decode_uncompressed(&[0x04, 9, 5]).unwrap()
+            );
+            assert_eq!(
+                Err(NOT_ON_THE_CURVE.to_string()),
+                curve.decode_uncompressed(&[0x04, 9, 6])
+            );
+            assert_eq!(
+                Err(OUT_OF_RANGE.to_string()),
+                curve.decode_uncompressed(&[0x04, 9 + 53, 5])
+            );
+            for bytes in [
+                &[][..],
+                &[0x04][..],
+                &[0x04, 9][..],
+                &[0x04, 9, 5, 0][..],
+                &[0x02, 9][..],
+                &[0x05, 9, 5][..],
+                &[0x00, 0][..],
+            ] {
+                assert_eq!(
+                    Err(INVALID_ENCODING.to_string()),
+                    curve.decode_uncompressed(bytes)
+                );
+            }
+        }
+        #[test]
+        fn test_affine_strict() {
+            let curve = EllipticCurve::new(3, 11, 53).unwrap();
+            assert_eq!(0..53, curve.valid_coordinates_range());
+            assert_eq!(Point::Affine(9, 5), curve.affine_strict(9, 5).unwrap());
+            assert_eq!(Point::Affine(38, 47), curve.affine_strict(38, 47).unwrap());
+            assert_eq!(Point::Affine(0, 8), curve.affine_strict(0, 8).unwrap());
+            for (x, y) in [(9 + 53, 5), (9, 5 - 53), (-15, -6), (53, 8), (0, 8 + 53)] {
+                assert_eq!(Err(OUT_OF_RANGE.to_string()), curve.affine_strict(x, y));
+                assert!(curve.affine(x, y).is_ok());
+            }
+            assert_eq!(Err(NOT_ON_THE_CURVE.to_string()), curve.affine_strict(9, 6));
+        }
+        #[test]
+        fn test_from_raw() {
+            let curve = EllipticCurve::new(3, 11, 53).unwrap();
+            assert_eq!(Point::Inf, curve.from_raw(0, 0, Some((0, 0))).unwrap());
+            assert_eq!(Point::Inf, curve.from_raw(53, -53, Some((0, 0))).unwrap());
+            assert!(curve.from_raw(0, 0, None).is_err());
+            assert_eq!(
+                Point::Affine(38, 47),
+                curve.from_raw(38, 47, Some((0, 0))).unwrap()
+            );
+            assert_eq!(
+                Point::Affine(38, 47),
+                curve.from_raw(38 - 53, 47, None).unwrap()
+            );
+            assert!(curve.from_raw(9, 6, Some((0, 0))).is_err());
+            let curve = EllipticCurve::new(-1, 0, 71).unwrap();
+            assert_eq!(Point::Affine(0, 0), curve.from_raw(0, 0, None).unwrap());
+            assert_eq!(Point::Inf, curve.from_raw(0, 0, Some((0, 0))).unwrap());
+            assert_eq!(Point::Inf, curve.from_raw(-1, -1, Some((70, 70))).unwrap());
+        }
+        #[test]
+        fn test_points_with_x() {
+            let curve = EllipticCurve::new(3, 11, 53).unwrap();
+            assert_eq!(
+                vec![Point::Affine(2, 5), Point::Affine(2, 48)],
+                curve.points_with_x(2).unwrap()
+            );
+            assert_eq!(
+                vec![Point::Affine(0, 8), Point::Affine(0, 45)],
+                curve.points_with_x(53).unwrap()
+            );
+            assert!(curve.points_with_x(4).unwrap().is_empty());
+            let curve = EllipticCurve::new(-1, 0, 71).unwrap();
+            assert_eq!(vec![Point::Affine(1, 0)], curve.points_with_x(1).unwrap());
+            assert_eq!(vec![Point::Affine(70, 0)], curve.points_with_x(-1).unwrap());
+            let mut points = vec![Point::Inf];
+            for x in 0..71 {
+                points.extend(curve.points_with_x(x).unwrap());
+            }
+            assert_eq!(curve.solutions().unwrap(), points);
+        }
+        #[test]
+        fn test_isomorphism() {
+            let curve = EllipticCurve::new(3, 11, 53).unwrap();
+            let target = EllipticCurve::new(3 * 16, 11 * 64, 53).unwrap();
+            let u = curve.isomorphism(&target).unwrap().unwrap();
+            assert_eq!(Some(1), curve.isomorphism(&curve).unwrap());
+            for pt in curve.solutions().unwrap() {
+                let mapped = curve.map_point(u, pt).unwrap();
+                assert!(target.is_on(mapped));
+            }
+            let (p0, p1) = (Point::Affine(9, 5), Point::Affine(38, 47));
+            assert_eq!(
+                curve.map_point(u, curve.sum(p0, p1).unwrap()).unwrap(),
+                target
+                    .sum(
+                        curve.map_point(u, p0).unwrap(),
+                        curve.map_point(u, p1).unwrap()
+                    )
+                    .unwrap()
+            );
+            assert_eq!(Point::Inf, curve.map_point(u, Point::Inf).unwrap());
+            assert!(curve.map_point(0, p0).is_err());
+            assert!(curve.map_point(u, Point::Affine(9, 6)).is_err());
+            let other = EllipticCurve::new(3, 12, 53).unwrap();
+            assert_eq!(None, curve.isomorphism(&other).unwrap());
+            let other = EllipticCurve::new(3, 11, 71).unwrap();
+            assert!(curve.isomorphism(&other).is_err());
+        }
+        #[test]
+        fn test_residue_set() {
+            let builds = || RESIDUE_SET_BUILDS.with(|n| n.get());
+            let curve = EllipticCurve::new(3, 11, 53).unwrap();
+            let before = builds();
+            let ord = curve.order().unwrap();
+            let sols = curve.solutions().unwrap();
+            curve.decomposition().unwrap();
+            curve.order_verbose().unwrap();
+            assert_eq!(before + 1, builds());
+            assert_eq!(57, ord);
+            assert_eq!(57, sols.len());
+            assert_eq!(53.qr_sieve().unwrap(), curve.residue_set().unwrap());
+            assert_eq!(before + 1, builds());
+            assert_eq!(curve, EllipticCurve::new(3, 11, 53).unwrap());
+        }
+        #[test]
+        fn test_new_checked() {
+            assert_eq!(
+                Ok(EllipticCurve::new(1, 1, 5).unwrap()),
+                EllipticCurve::new_checked(1, 1, 5)
+            );
+            assert_eq!(
+                Err(CurveError::Singular(-496)),
+                EllipticCurve::new_checked(1, 1, 31)
+            );
+            assert!(EllipticCurve::new(1, 1, 31).is_ok());
+            assert_eq!(
+                Err(CurveError::Singular(0)),
+                EllipticCurve::new_checked(-3, 2, 7)
+            );
+            assert_eq!(
+                Err(CurveError::Singular(0)),
+                EllipticCurve::new_checked(0, 0, 7)
+            );
+            assert_eq!(
+                Err(CurveError::Field(NOT_A_PRIME.to_string())),
+                EllipticCurve::new_checked(1, 1, 9)
+            );
+            assert_eq!(
+                "not an non-singular... (discriminant -496)",
+                EllipticCurve::new_checked(1, 1, 31)
+                    .unwrap_err()
+                    .to_string()
+            );
+            let b = 1 + 31 * 1_000_000;
+            assert_eq!(
+                Err(CurveError::Singular(-16 * (4 + 27 * (b as i128).pow(2)))),
+                EllipticCurve::new_checked(1, b, 31)
+            );
+        }
+        #[test]
+        fn test_setters() {
+            let builds = || RESIDUE_SET_BUILDS.with(|n| n.get());
+            let mut curve = EllipticCurve::new(3, 11, 53).unwrap();
+            assert_eq!(57, curve.order().unwrap());
+            let expected = EllipticCurve::new(3, -1, 53).unwrap().order();
+            let before = builds();
+            curve.set_coef0(-1).unwrap();
+            assert_eq!((3, 52, 53), (curve.coef1(), curve.coef0(), curve.prime()));
+            assert_eq!(expected, curve.order());
+            assert_ne!(Ok(57), curve.order());
+            assert_eq!(before, builds());
+            curve.set_coef1(-1).unwrap();
+            curve.set_coef0(0).unwrap();
+            assert_eq!(EllipticCurve::new(-1, 0, 53).unwrap(), curve);
+            assert_eq!(
+                EllipticCurve::new(-1, 0, 53).unwrap().order(),
+                curve.order()
+            );
+            assert_eq!(Err(NOT_AN_NON_SINGULAR.to_string()), curve.set_coef1(53));
+            assert_eq!((52, 0), (curve.coef1(), curve.coef0()));
+        }
+        #[test]
+        fn test_quadratic_twist() {
+            let curve = EllipticCurve::new(2, 3, 97).unwrap();
+            let twist = curve.quadratic_twist().unwrap();
+            assert_eq!((50, 84), (twist.coef1, twist.coef0));
+            assert_eq!(curve.j_invariant(), twist.j_invariant());
+            let prime = 23;
+            for a in 0..prime {
+                for b in 0..prime {
+                    if (4 * a * a * a + 27 * b * b) % prime == 0 {
+                        continue;
+                    }
+                    let curve = EllipticCurve::new(a, b, prime).unwrap();
+                    let twist = curve.quadratic_twist().unwrap();
+                    assert_eq!(
+                        2 * (prime + 1),
+                        curve.order().unwrap() + twist.order().unwrap()
+                    );
+                }
+            }
+        }
+    }
+    mod test_op {
+        use super::*;
+
+        fn curve_points(curve: &EllipticCurve) -> Vec<Point> {
+            let mut points = vec![Point::Inf];
+            for x in 0..curve.prime {
+                for y in 0..curve.prime {
+                    if curve.is_on(Point::Affine(x, y)) {
+                        points.push(Point::Affine(x, y));
+                    }
+                }
+            }
+            points
+        }
+
+        #[test]
+        fn test_lhs() {
+            let curve = EllipticCurve::new(9, 20, 7).unwrap();
+            assert_eq!(4, curve.lhs(2).unwrap());
+        }
+        #[test]
+        fn test_rhs() {
+            let curve = EllipticCurve::new(9, 20, 7).unwrap();
+            assert_eq!(4, curve.rhs(2).unwrap());
+        }
+        #[test]
+        fn test_is_on() {
+            let curve = EllipticCurve::new(7, 5, 13).unwrap();
+            assert!(curve.is_on(Point::Inf));
+            assert!(curve.is_on(Point::Affine(5, 10)));
+            assert!(curve.is_on(Point::Affine(9, 2)));
+            assert!(curve.is_on(Point::Affine(8, 1)));
+            assert!(curve.is_on(Point::Affine(8 + 13, 1 + 13)));
+            assert!(!curve.is_on(Point::Affine(9, 1)));
+
+            let curve = EllipticCurve::new(77, 42, 97).unwrap();
+            assert!(curve.is_on(Point::Inf));
+            assert!(curve.is_on(Point::Affine(22, 68)));
+            assert!(curve.is_on(Point::Affine(64, 48)));
+            assert!(!curve.is_on(Point::Affine(35, 54)));
+            assert!(!curve.is_on(Point::Affine(35, 65)));
+            assert!(!curve.is_on(Point::Affine(10, 10)));
+        }
+        #[test]
+        fn test_is_on_affine() {
+            let curve = EllipticCurve::new(7, 5, 13).unwrap();
+            assert!(curve.is_on_affine(5, 10));
+            assert!(curve.is_on_affine(9, 2));
+            assert!(curve.is_on_affine(8, 1));
+            assert!(curve.is_on_affine(8 + 13, 1 + 13));
+            assert!(curve.is_on_affine(8 - 13, 1 - 26));
+            assert!(!curve.is_on_affine(9, 1));
+            assert!(!curve.is_on_affine(0, 0));
+
+            let curve = EllipticCurve::new(77, 42, 97).unwrap();
+            assert!(curve.is_on_affine(22, 68));
+            assert!(curve.is_on_affine(64, 48));
+            assert!(!curve.is_on_affine(35, 54));
+            assert!(!curve.is_on_affine(35, 65));
+            assert!(!curve.is_on_affine(10, 10));
+            for x in 0..97 {
+                for y in 0..97 {
+                    assert_eq!(curve.is_on(Point::Affine(x, y)), curve.is_on_affine(x, y));
+                }
+            }
+        }
+        #[test]
+        fn test_is_on_projective() {
+            let curve = EllipticCurve::new(77, 42, 97).unwrap();
+            assert!(curve.is_on_projective(22, 68, 1));
+            assert!(curve.is_on_projective(22 * 5, 68 * 5, 5));
+            assert!(!curve.is_on_projective(35, 54, 1));
+            assert!(curve.is_on_projective(0, 1, 0));
+            assert!(curve.is_on_projective(3, 7, 97));
+            for x in 0..97 {
+                for y in 0..97 {
+                    for z in [1, 2, 45, 96, -3, 97 + 10] {
+                        assert_eq!(
+                            curve.is_on(Point::Affine(x, y)),
+                            curve.is_on_projective(x * z, y * z, z)
+                        );
+                    }
+                }
+            }
+            let curve = EllipticCurve::new(7, 5, 13).unwrap();
+            let mut rng = XorShift::new(0x9e55);
+            for pt in curve_points(&curve) {
+                let (x, y) = pt.coordinates().unwrap_or((0, 1));
+                let z = 1 + rng.below(12);
+                let z = if pt.is_inf() { 0 } else { z };
+                assert!(curve.is_on_projective(x * z, y * z, z));
+                for k in 0..20 {
+                    let q = curve.proj_mul(k, curve.to_projective(pt));
+                    assert!(curve.is_on_projective(q.x, q.y, q.z));
+                }
+            }
+        }
+        #[test]
+        fn test_is_on_out_of_range() {
+            let curve = EllipticCurve::new(7, 5, 13).unwrap();
+            for x in 0..13 {
+                for y in 0..13 {
+                    let expected = curve.is_on(Point::Affine(x, y));
+                    for (kx, ky) in [
+                        (1, 1),
+                        (-1, 3),
+                        (100_000, -100_000),
+                        (-165_000_000, 165_000_000),
+                    ] {
+                        assert_eq!(
+                            expected,
+                            curve.is_on(Point::Affine(x + 13 * kx, y + 13 * ky))
+                        );
+                    }
+                }
+            }
+            assert!(curve.is_on(Point::Affine(5 - 13 * 165_000_000, 10 + 13 * 165_000_000)));
+        }
+        #[test]
+        fn test_identity() {
+            for (a, b, p) in [(7, 5, 13), (-1, 0, 71), (3, 11, 53)] {
+                let curve = EllipticCurve::new(a, b, p).unwrap();
+                assert_eq!(Point::Inf, curve.normalize(Point::Inf));
+                assert_eq!(Ok(Point::Inf), curve.represent(Point::Inf));
+                assert_eq!(Ok(Point::Inf), curve.checked_represent(Point::Inf));
+                assert_eq!(Ok(Point::Inf), curve.inv(Point::Inf));
+                assert_eq!(Point::Inf, curve.sum(Point::Inf, Point::Inf).unwrap());
+                for pt in curve_points(&curve) {
+                    let raw = match pt {
+                        Point::Inf => Point::Inf,
+                        Point::Affine(x, y) => Point::Affine(x + p, y - 2 * p),
+                    };
+                    assert_eq!(pt, curve.sum(raw, Point::Inf).unwrap());
+                    assert_eq!(pt, curve.sum(Point::Inf, raw).unwrap());
+                    let neg = curve.inv(raw).unwrap();
+                    assert_eq!(Point::Inf, curve.sum(raw, neg).unwrap());
+                    assert_eq!(Point::Inf, curve.sum(neg, raw).unwrap());
+                    assert_eq!(pt.is_inf(), neg.is_inf());
+                    if pt.y() == Some(0) {
+                        assert_eq!(pt, neg);
+                        assert_eq!(Point::Inf, curve.sum(pt, pt).unwrap());
+                    }
+                    assert_eq!(Point::Inf, curve.mul(0, raw).unwrap());
+                }
+                assert_eq!(Point::Inf, curve.mul(5, Point::Inf).unwrap());
+            }
+            // An off-curve affine point is never silently read as the identity.
+            let curve = EllipticCurve::new(7, 5, 13).unwrap();
+            let zero = Point::Affine(0, 0);
+            assert!(curve.represent(zero).is_err());
+            assert!(curve.inv(zero).is_err());
+            assert!(curve.sum(zero, Point::Inf).is_err());
+            assert!(curve.sum(Point::Inf, zero).is_err());
+            assert_eq!(Point::Inf, curve.from_raw(0, 0, Some((0, 0))).unwrap());
+        }
+        #[test]
+        fn test_represent() {
+            let curve = EllipticCurve::new(7, 5, 13).unwrap();
+            let pt = Point::Affine(3, 1);
+            assert_eq!(pt, curve.represent(Point::Affine(3 + 13, 1 + 13)).unwrap());
+            assert_eq!(
+                pt,
+                curve
                     .represent(Point::Affine(3 + 13 * 2, 1 + 13 * 2))
                     .unwrap()
             );
@@ -184,6 +1724,69 @@ mod test {
             );
         }
         #[test]
+        fn test_checked_represent() {
+            let curve = EllipticCurve::new(3, 11, 53).unwrap();
+            assert_eq!(Ok(Point::Inf), curve.checked_represent(Point::Inf));
+            assert_eq!(
+                Ok(Point::Affine(9, 5)),
+                curve.checked_represent(Point::Affine(9 + 53, 5 - 53))
+            );
+            assert_eq!(
+                Err(CurveError::NotOnCurve),
+                curve.checked_represent(Point::Affine(9, 6))
+            );
+            let broken = EllipticCurve {
+                coef1: 3,
+                coef0: 11,
+                prime: -53,
+                residues: OnceCell::new(),
+                order: OnceCell::new(),
+                order_factors: OnceCell::new(),
+            };
+            assert_eq!(
+                Err(CurveError::Field(NOT_A_POS.to_string())),
+                broken.checked_represent(Point::Affine(9, 5))
+            );
+            assert_eq!(NOT_ON_THE_CURVE, CurveError::NotOnCurve.to_string());
+        }
+        #[test]
+        fn test_normalize() {
+            let curve = EllipticCurve::new(7, 5, 13).unwrap();
+            let mut points = std::collections::HashSet::new();
+            for pt in [
+                Point::Inf,
+                Point::Affine(3, 1),
+                Point::Affine(3 + 13, 1 + 13),
+                Point::Affine(3 - 13 * 4, 1 + 13 * 7),
+                Point::Affine(5, 10),
+            ] {
+                points.insert(curve.normalize(pt));
+            }
+            assert_eq!(3, points.len());
+            assert!(points.contains(&Point::Inf));
+            assert!(points.contains(&Point::Affine(3, 1)));
+            assert!(points.contains(&Point::Affine(5, 10)));
+        }
+        #[test]
+        fn test_normalize_unchecked() {
+            let curve = EllipticCurve::new(7, 5, 13).unwrap();
+            assert_eq!(Point::Inf, curve.normalize(Point::Inf));
+            assert_eq!(
+                Point::Affine(12, 1),
+                curve.normalize(Point::Affine(-1, -12))
+            );
+            assert_eq!(
+                Point::Affine(3, 1),
+                curve.normalize(Point::Affine(3 - 13 * 1000, 1 - 13 * 77))
+            );
+            assert_eq!(
+                Point::Affine(Num::MAX % 13, 0),
+                curve.normalize(Point::Affine(Num::MAX, 13 * 1000))
+            );
+            assert_eq!(Point::Affine(9, 1), curve.normalize(Point::Affine(9, 14)));
+            assert!(curve.represent(Point::Affine(9, 14)).is_err());
+        }
+        #[test]
         fn test_inv() {
             let curve = EllipticCurve::new(11, 3, 67).unwrap();
             assert_eq!(Point::Inf, curve.inv(Point::Inf).unwrap());
@@ -213,6 +1816,544 @@ mod test {
                     .sum(Point::Affine(13, 22), Point::Affine(6, 38))
                     .unwrap()
             );
+            let mut rng = XorShift::new(9);
+            for prime in [50021, 1_000_000_007] {
+                let curve = EllipticCurve::new(2, 3, prime).unwrap();
+                let pt = curve.sample_point(&mut rng).unwrap();
+                let twice = curve.sum(pt, pt).unwrap();
+                assert_eq!(curve.mul(2, pt).unwrap(), twice);
+                assert_eq!(curve.mul(3, pt).unwrap(), curve.sum(twice, pt).unwrap());
+            }
+        }
+        #[test]
+        fn test_mul_trace() {
+            let curve = EllipticCurve::new(23, 9, 47).unwrap();
+            let pt = Point::Affine(13, 22);
+            let (res, log) = curve.mul_trace(11, pt).unwrap();
+            assert_eq!(curve.mul(11, pt).unwrap(), res);
+            assert_eq!(
+                vec![
+                    "bit=1: add",
+                    "bit=0: double only",
+                    "bit=1: add",
+                    "bit=1: add"
+                ],
+                log
+            );
+            assert_eq!((Point::Inf, vec![]), curve.mul_trace(0, pt).unwrap());
+            for k in 1..=130 {
+                let (res, log) = curve.mul_trace(k, pt).unwrap();
+                assert_eq!(curve.mul(k, pt).unwrap(), res);
+                assert_eq!((Num::BITS - k.leading_zeros()) as usize, log.len());
+                assert_eq!(
+                    k.count_ones() as usize,
+                    log.iter().filter(|l| l.ends_with("add")).count()
+                );
+            }
+            for k in [-1, -2, -57, -200, Num::MIN + 1, Num::MIN] {
+                let (res, log) = curve.mul_trace(k, pt).unwrap();
+                assert_eq!(curve.mul(k, pt).unwrap(), res);
+                assert_eq!(
+                    (u32::BITS - k.unsigned_abs().leading_zeros()) as usize,
+                    log.len()
+                );
+            }
+            assert!(curve.mul_trace(3, Point::Affine(0, 0)).is_err());
+        }
+        #[test]
+        fn test_sum_explain() {
+            let curve = EllipticCurve::new(23, 9, 47).unwrap();
+            let (p0, p1) = (Point::Affine(13, 22), Point::Affine(6, 38));
+            assert_eq!(
+                (Point::Affine(15, 43), "generic"),
+                curve.sum_explain(p0, p1).unwrap()
+            );
+            assert_eq!((p0, "identity"), curve.sum_explain(p0, Point::Inf).unwrap());
+            assert_eq!((p1, "identity"), curve.sum_explain(Point::Inf, p1).unwrap());
+            assert_eq!(
+                (Point::Inf, "identity"),
+                curve.sum_explain(Point::Inf, Point::Inf).unwrap()
+            );
+            let inv = curve.inv(p0).unwrap();
+            assert_eq!(
+                (Point::Inf, "inverse->Inf"),
+                curve.sum_explain(p0, inv).unwrap()
+            );
+            assert_eq!(
+                (curve.sum(p0, p0).unwrap(), "doubling"),
+                curve.sum_explain(p0, Point::Affine(13 + 47, 22)).unwrap()
+            );
+            for q0 in curve_points(&curve) {
+                for q1 in curve_points(&curve) {
+                    assert_eq!(
+                        curve.sum(q0, q1).unwrap(),
+                        curve.sum_explain(q0, q1).unwrap().0
+                    );
+                }
+            }
+            assert!(curve.sum_explain(p0, Point::Affine(13, 23)).is_err());
+            let p = 2147390939;
+            let curve = EllipticCurve::new(2, 3, p).unwrap();
+            let pt = (1..p)
+                .flat_map(|x| curve.points_with_x(x).unwrap())
+                .find(|pt| pt.y().unwrap() > p / 2)
+                .unwrap();
+            assert_eq!(
+                (curve.sum(pt, pt).unwrap(), "doubling"),
+                curve.sum_explain(pt, pt).unwrap()
+            );
+            assert_eq!(
+                (Point::Inf, "inverse->Inf"),
+                curve.sum_explain(pt, curve.inv(pt).unwrap()).unwrap()
+            );
+        }
+        #[test]
+        fn test_sum_unreduced() {
+            for (a, b, p) in [(23, 9, 47), (-1, 0, 71), (3, 11, 53)] {
+                let curve = EllipticCurve::new(a, b, p).unwrap();
+                let points = curve_points(&curve);
+                for (i, p0) in points.iter().enumerate() {
+                    let (p0, p1) = (*p0, points[(i * 7 + 3) % points.len()]);
+                    let expected = curve.sum(p0, p1).unwrap();
+                    let shift = |pt: Point, k: Num| match pt {
+                        Point::Inf => Point::Inf,
+                        Point::Affine(x, y) => Point::Affine(x + k * p, y - (k + 1) * p),
+                    };
+                    for k in [1, 5, -3] {
+                        assert_eq!(expected, curve.sum(shift(p0, k), p1).unwrap());
+                        assert_eq!(expected, curve.sum(p0, shift(p1, k)).unwrap());
+                        assert_eq!(expected, curve.sum(shift(p0, k), shift(p1, -k)).unwrap());
+                    }
+                    let double = curve.sum(p0, p0).unwrap();
+                    assert_eq!(double, curve.sum(shift(p0, 1), shift(p0, 2)).unwrap());
+                }
+            }
+        }
+        #[test]
+        fn test_sum_inverse() {
+            for (a, b, p) in [(23, 9, 47), (-1, 0, 71), (7, 5, 13)] {
+                let curve = EllipticCurve::new(a, b, p).unwrap();
+                for pt in curve_points(&curve) {
+                    let inv = curve.inv(pt).unwrap();
+                    assert_eq!(Point::Inf, curve.sum(pt, inv).unwrap());
+                    assert_eq!(Point::Inf, curve.sum(inv, pt).unwrap());
+                    if let Point::Affine(x, y) = inv {
+                        let raw = Point::Affine(x + 3 * p, y - 2 * p);
+                        assert_eq!(Point::Inf, curve.sum(pt, raw).unwrap());
+                    }
+                }
+            }
+            let curve = EllipticCurve::new(-1, 0, 71).unwrap();
+            for pt in [
+                Point::Affine(0, 0),
+                Point::Affine(1, 0),
+                Point::Affine(70, 0),
+            ] {
+                assert_eq!(pt, curve.inv(pt).unwrap());
+                assert_eq!(Point::Inf, curve.sum(pt, pt).unwrap());
+            }
+        }
+        #[test]
+        fn test_mul_projective() {
+            for (a, b, p) in [(23, 9, 47), (3, 11, 53), (-1, 0, 71), (7, 5, 13)] {
+                let curve = EllipticCurve::new(a, b, p).unwrap();
+                for pt in curve_points(&curve) {
+                    let mut acc = Point::Inf;
+                    for k in 0..2 * p {
+                        assert_eq!(acc, curve.mul_projective(k, pt).unwrap());
+                        acc = curve.sum(acc, pt).unwrap();
+                    }
+                }
+            }
+        }
+        #[test]
+        fn test_mul() {
+            let curve = EllipticCurve::new(3, 11, 53).unwrap();
+            let pt = Point::Affine(9, 5);
+            assert_eq!(Point::Inf, curve.mul(0, pt).unwrap());
+            assert_eq!(pt, curve.mul(1, pt).unwrap());
+            assert_eq!(pt, curve.mul(1, Point::Affine(9 + 53, 5 - 53)).unwrap());
+            assert_eq!(curve.sum(pt, pt).unwrap(), curve.mul(2, pt).unwrap());
+            assert_eq!(Point::Inf, curve.mul(57, pt).unwrap());
+            assert_eq!(Point::Inf, curve.mul(5, Point::Inf).unwrap());
+            assert!(curve.mul(2, Point::Affine(9, 6)).is_err());
+        }
+        #[test]
+        fn test_mul_in_subgroup() {
+            let curve = EllipticCurve::new(3, 11, 53).unwrap();
+            for (pt, n) in [(Point::Affine(9, 5), 57), (Point::Affine(38, 47), 19)] {
+                for k in (-150..150).chain([Num::MAX, Num::MIN]) {
+                    assert_eq!(
+                        curve.mul(k, pt).unwrap(),
+                        curve.mul_in_subgroup(k, pt, n).unwrap()
+                    );
+                }
+                assert_eq!(
+                    curve.mul(5, pt).unwrap(),
+                    curve.mul_in_subgroup(5, pt, 2 * n).unwrap()
+                );
+                assert_eq!(
+                    Err(WRONG_ORDER.to_string()),
+                    curve.mul_in_subgroup(5, pt, n - 1)
+                );
+                assert!(curve.mul_in_subgroup(5, pt, 0).is_err());
+                assert!(curve.mul_in_subgroup(5, pt, -n).is_err());
+            }
+            assert_eq!(
+                Err(WRONG_ORDER.to_string()),
+                curve.mul_in_subgroup(2, Point::Affine(9, 5), 19)
+            );
+            assert_eq!(Point::Inf, curve.mul_in_subgroup(7, Point::Inf, 1).unwrap());
+            assert!(curve.mul_in_subgroup(1, Point::Affine(9, 6), 19).is_err());
+        }
+        #[test]
+        fn test_mul_negative() {
+            let curve = EllipticCurve::new(3, 11, 53).unwrap();
+            for (pt, n) in [(Point::Affine(9, 5), 57), (Point::Affine(38, 47), 19)] {
+                assert_eq!(curve.inv(pt).unwrap(), curve.mul(-1, pt).unwrap());
+                assert_eq!(
+                    curve.inv(curve.mul(3, pt).unwrap()).unwrap(),
+                    curve.mul(-3, pt).unwrap()
+                );
+                assert_eq!(Point::Inf, curve.mul(-n, pt).unwrap());
+                for k in 1..2 * n {
+                    assert_eq!(curve.mul(n - k, pt).unwrap(), curve.mul(-k, pt).unwrap());
+                }
+                let max = curve.mul(Num::MAX, pt).unwrap();
+                assert_eq!(
+                    curve.inv(curve.sum(max, pt).unwrap()).unwrap(),
+                    curve.mul(Num::MIN, pt).unwrap()
+                );
+            }
+            assert_eq!(Point::Inf, curve.mul(-5, Point::Inf).unwrap());
+            assert!(curve.mul(-2, Point::Affine(9, 6)).is_err());
+        }
+        #[test]
+        fn test_mul_ladder() {
+            let curve = EllipticCurve::new(3, 11, 53).unwrap();
+            for pt in curve_points(&curve) {
+                for k in 0..200 {
+                    assert_eq!(curve.mul(k, pt).unwrap(), curve.mul_ladder(k, pt).unwrap());
+                }
+            }
+            let pt = Point::Affine(9, 5);
+            assert_eq!(
+                curve.mul(Num::MAX, pt).unwrap(),
+                curve.mul_ladder(Num::MAX, pt).unwrap()
+            );
+            for k in [-1, -2, -57, -200, Num::MIN + 1, Num::MIN] {
+                assert_eq!(curve.mul(k, pt).unwrap(), curve.mul_ladder(k, pt).unwrap());
+            }
+            assert!(curve.mul_ladder(2, Point::Affine(9, 6)).is_err());
+        }
+        #[test]
+        fn test_mul_double() {
+            let curve = EllipticCurve::new(3, 11, 53).unwrap();
+            let points = curve_points(&curve);
+            for (i, &p0) in points.iter().enumerate().step_by(7) {
+                for &p1 in points.iter().skip(i).step_by(5) {
+                    for k0 in [0, 1, 2, 5, 19, 56, 57, 100] {
+                        for k1 in [0, 1, 3, 18, 57, 64] {
+                            assert_eq!(
+                                curve
+                                    .sum(curve.mul(k0, p0).unwrap(), curve.mul(k1, p1).unwrap())
+                                    .unwrap(),
+                                curve.mul_double(k0, p0, k1, p1).unwrap()
+                            );
+                        }
+                    }
+                }
+            }
+            let pt = Point::Affine(9, 5);
+            assert_eq!(
+                curve.mul(7, pt).unwrap(),
+                curve.mul_double(3, pt, 4, pt).unwrap()
+            );
+            let other = curve.mul(3, pt).unwrap();
+            for k0 in [-1, -2, -57, -200, Num::MIN + 1, Num::MIN] {
+                for k1 in [-5, 0, 7, Num::MIN] {
+                    assert_eq!(
+                        curve
+                            .sum(curve.mul(k0, pt).unwrap(), curve.mul(k1, other).unwrap())
+                            .unwrap(),
+                        curve.mul_double(k0, pt, k1, other).unwrap()
+                    );
+                }
+            }
+            assert!(curve.mul_double(1, pt, 1, Point::Affine(9, 6)).is_err());
+        }
+        #[test]
+        fn test_mul_wnaf() {
+            let mut rng = XorShift::new(305);
+            let curve = EllipticCurve::new(3, 11, 53).unwrap();
+            for pt in curve_points(&curve) {
+                for window in 2..=6 {
+                    for _ in 0..20 {
+                        let k = rng.below(Num::MAX);
+                        assert_eq!(
+                            curve.mul(k, pt).unwrap(),
+                            curve.mul_wnaf(k, pt, window).unwrap()
+                        );
+                    }
+                    for k in 0..100 {
+                        assert_eq!(
+                            curve.mul(k, pt).unwrap(),
+                            curve.mul_wnaf(k, pt, window).unwrap()
+                        );
+                    }
+                }
+            }
+            let pt = Point::Affine(9, 5);
+            for window in 2..=6 {
+                assert_eq!(
+                    curve.mul(Num::MAX, pt).unwrap(),
+                    curve.mul_wnaf(Num::MAX, pt, window).unwrap()
+                );
+            }
+            assert!(curve.mul_wnaf(10, pt, 0).is_err());
+            assert!(curve.mul_wnaf(10, pt, 1).is_err());
+            assert!(curve.mul_wnaf(10, pt, 7).is_err());
+            assert!(curve.mul_wnaf(100, Point::Affine(9, 6), 3).is_err());
+        }
+        #[test]
+        fn test_checked_sum() {
+            for (a, b, p) in [(23, 9, 47), (3, 11, 53), (-1, 0, 71)] {
+                let curve = EllipticCurve::new(a, b, p).unwrap();
+                let points = curve_points(&curve);
+                for &pt0 in &points {
+                    for &pt1 in &points {
+                        assert_eq!(
+                            curve.sum(pt0, pt1).unwrap(),
+                            curve.checked_sum(pt0, pt1).unwrap()
+                        );
+                    }
+                }
+            }
+            let curve = EllipticCurve::new(1, -1, 1_000_000_007).unwrap();
+            let pt = Point::Affine(2, 3);
+            assert!(curve.is_on(pt));
+            assert_eq!(Err(OVERFLOW.to_string()), curve.checked_sum(pt, pt));
+            assert_eq!(Err(OVERFLOW.to_string()), curve.checked_mul(2, pt));
+            assert_eq!(pt, curve.checked_mul(1, pt).unwrap());
+        }
+        #[test]
+        fn test_checked_mul() {
+            let curve = EllipticCurve::new(3, 11, 53).unwrap();
+            for pt in curve_points(&curve) {
+                for k in 0..120 {
+                    assert_eq!(curve.mul(k, pt).unwrap(), curve.checked_mul(k, pt).unwrap());
+                }
+            }
+            for k in [-1, -2, -57, -200, Num::MIN + 1, Num::MIN] {
+                let pt = Point::Affine(9, 5);
+                assert_eq!(curve.mul(k, pt).unwrap(), curve.checked_mul(k, pt).unwrap());
+            }
+            assert!(curve.checked_mul(3, Point::Affine(9, 6)).is_err());
+        }
+        #[test]
+        fn test_mul_many() {
+            let curve = EllipticCurve::new(3, 11, 53).unwrap();
+            assert_eq!(Vec::<Point>::new(), curve.mul_many(&[]).unwrap());
+            let mut pairs = Vec::new();
+            for (i, pt) in curve_points(&curve).into_iter().enumerate() {
+                pairs.push((i as Num * 7, pt));
+                pairs.push((57, pt));
+            }
+            let expected: Vec<Point> = pairs
+                .iter()
+                .map(|&(k, pt)| curve.mul(k, pt).unwrap())
+                .collect();
+            assert_eq!(expected, curve.mul_many(&pairs).unwrap());
+            assert!(curve
+                .mul_many(&[(1, Point::Affine(9, 5)), (1, Point::Affine(9, 6))])
+                .is_err());
+            let pairs: Vec<(Num, Point)> = [-1, -2, -57, -200, Num::MIN + 1, Num::MIN]
+                .into_iter()
+                .map(|k| (k, Point::Affine(9, 5)))
+                .collect();
+            let expected: Vec<Point> = pairs
+                .iter()
+                .map(|&(k, pt)| curve.mul(k, pt).unwrap())
+                .collect();
+            assert_eq!(expected, curve.mul_many(&pairs).unwrap());
+        }
+        #[test]
+        fn test_sum_chain() {
+            let mut rng = XorShift::new(0xc4a1);
+            for (a, b, p) in [(3, 11, 53), (-1, 0, 71), (2, 3, 97), (57, 97, 199)] {
+                let curve = EllipticCurve::new(a, b, p).unwrap();
+                assert_eq!(Point::Inf, curve.sum_chain(&[]).unwrap());
+                for len in 1..40 {
+                    let points: Vec<Point> = (0..len)
+                        .map(|_| curve.sample_point(&mut rng).unwrap())
+                        .collect();
+                    let naive = points
+                        .iter()
+                        .try_fold(Point::Inf, |acc, pt| curve.sum(acc, *pt))
+                        .unwrap();
+                    assert_eq!(naive, curve.sum_chain(&points).unwrap());
+                }
+            }
+            let curve = EllipticCurve::new(3, 11, 53).unwrap();
+            let pt = Point::Affine(38, 47);
+            assert_eq!(
+                curve.mul(3, pt).unwrap(),
+                curve
+                    .sum_chain(&[pt, Point::Affine(38 + 53, 47 - 53), pt])
+                    .unwrap()
+            );
+            assert!(curve.sum_chain(&[pt, Point::Affine(9, 6), pt]).is_err());
+        }
+        #[test]
+        fn test_sum_all() {
+            let curve = EllipticCurve::new(3, 11, 53).unwrap();
+            assert_eq!(Point::Inf, curve.sum_all(&[]).unwrap());
+            let pt = Point::Affine(38, 47);
+            assert_eq!(pt, curve.sum_all(&[pt]).unwrap());
+            assert_eq!(
+                curve.mul(3, pt).unwrap(),
+                curve.sum_all(&[pt, pt, pt]).unwrap()
+            );
+            let mut subgroup = vec![Point::Inf];
+            for k in 1..19 {
+                subgroup.push(curve.mul(k, pt).unwrap());
+            }
+            assert_eq!(Point::Inf, curve.sum_all(&subgroup).unwrap());
+            assert!(curve.sum_all(&[pt, Point::Affine(9, 6), pt]).is_err());
+            let curve = EllipticCurve::new(-1, 0, 71).unwrap();
+            let points = curve_points(&curve);
+            assert_eq!(Point::Inf, curve.sum_all(&points).unwrap());
+            let two_torsion = [Point::Affine(0, 0), Point::Affine(1, 0)];
+            assert_eq!(Point::Affine(70, 0), curve.sum_all(&two_torsion).unwrap());
+        }
+        #[test]
+        fn test_base_mul() {
+            for (a, b, p) in [(3, 11, 53), (-1, 0, 71), (2, 3, 97)] {
+                let curve = EllipticCurve::new(a, b, p).unwrap();
+                for gene in curve_points(&curve) {
+                    let table = curve.precompute_base(gene).unwrap();
+                    assert_eq!(gene, table.generator());
+                    for k in (0..150).chain([1 << 20, Num::MAX - 1, Num::MAX]) {
+                        assert_eq!(
+                            curve.mul(k, gene).unwrap(),
+                            curve.base_mul(&table, k).unwrap()
+                        );
+                    }
+                    for k in [-1, -2, -57, -200, Num::MIN + 1, Num::MIN] {
+                        assert_eq!(
+                            curve.mul(k, gene).unwrap(),
+                            curve.base_mul(&table, k).unwrap()
+                        );
+                    }
+                }
+            }
+            let curve = EllipticCurve::new(3, 11, 53).unwrap();
+            assert!(curve.precompute_base(Point::Affine(9, 6)).is_err());
+            let table = curve.precompute_base(Point::Affine(9 + 53, 5)).unwrap();
+            assert_eq!(Point::Affine(9, 5), table.generator());
+        }
+        #[test]
+        fn test_add_into() {
+            let curve = EllipticCurve::new(3, 11, 53).unwrap();
+            let pt = Point::Affine(38, 47);
+            let subgroup = curve.cyclic_group(pt).unwrap();
+            let mut acc = Point::Inf;
+            for (i, q) in subgroup.iter().enumerate() {
+                curve.add_into(&mut acc, *q).unwrap();
+                assert_eq!(curve.sum_all(&subgroup[..=i]).unwrap(), acc);
+            }
+            let mut acc = Point::Inf;
+            for _ in 0..5 {
+                curve.add_into(&mut acc, pt).unwrap();
+            }
+            assert_eq!(curve.mul(5, pt).unwrap(), acc);
+            assert!(curve.add_into(&mut acc, Point::Affine(9, 6)).is_err());
+            assert_eq!(curve.mul(5, pt).unwrap(), acc);
+        }
+    }
+    // Seeded random checks of the group laws, with the in-tree `XorShift`
+    // rather than a property-testing crate.
+    #[cfg(feature = "slow-tests")]
+    mod test_axioms {
+        use super::*;
+
+        const PRIMES: [Num; 9] = [5, 11, 53, 71, 199, 1009, 10007, 50021, 1_000_000_007];
+        const CASES: usize = 256;
+
+        fn arbitrary_point(curve: &EllipticCurve, rng: &mut XorShift) -> Point {
+            match rng.below(16) {
+                0 => Point::Inf,
+                _ => curve.sample_point(rng).unwrap_or(Point::Inf),
+            }
+        }
+        fn arbitrary_case(rng: &mut XorShift) -> (EllipticCurve, [Point; 3]) {
+            let prime = PRIMES[rng.below(PRIMES.len() as Num) as usize];
+            let curve = loop {
+                if let Ok(curve) =
+                    EllipticCurve::new_checked(rng.below(prime), rng.below(prime), prime)
+                {
+                    break curve;
+                }
+            };
+            let points = [
+                arbitrary_point(&curve, rng),
+                arbitrary_point(&curve, rng),
+                arbitrary_point(&curve, rng),
+            ];
+            (curve, points)
+        }
+
+        #[test]
+        fn test_identity() {
+            let mut rng = XorShift::new(1);
+            for _ in 0..CASES {
+                let (curve, [pt, _, _]) = arbitrary_case(&mut rng);
+                assert_eq!(pt, curve.sum(pt, Point::Inf).unwrap());
+                assert_eq!(pt, curve.sum(Point::Inf, pt).unwrap());
+            }
+        }
+        #[test]
+        fn test_inverse() {
+            let mut rng = XorShift::new(2);
+            for _ in 0..CASES {
+                let (curve, [pt, _, _]) = arbitrary_case(&mut rng);
+                let inv = curve.inv(pt).unwrap();
+                assert!(curve.is_on(inv));
+                assert_eq!(Point::Inf, curve.sum(pt, inv).unwrap());
+            }
+        }
+        #[test]
+        fn test_commutativity() {
+            let mut rng = XorShift::new(3);
+            for _ in 0..CASES {
+                let (curve, [p0, p1, _]) = arbitrary_case(&mut rng);
+                assert_eq!(curve.sum(p0, p1).unwrap(), curve.sum(p1, p0).unwrap());
+            }
+        }
+        #[test]
+        fn test_associativity() {
+            let mut rng = XorShift::new(4);
+            for _ in 0..CASES {
+                let (curve, [p0, p1, p2]) = arbitrary_case(&mut rng);
+                let lhs = curve.sum(curve.sum(p0, p1).unwrap(), p2).unwrap();
+                let rhs = curve.sum(p0, curve.sum(p1, p2).unwrap()).unwrap();
+                assert!(curve.is_on(lhs));
+                assert_eq!(lhs, rhs);
+            }
+        }
+        #[test]
+        fn test_mul_distributes() {
+            let mut rng = XorShift::new(5);
+            for _ in 0..CASES {
+                let (curve, [pt, _, _]) = arbitrary_case(&mut rng);
+                let (a, b) = (rng.below(1 << 15), rng.below(1 << 15));
+                assert_eq!(
+                    curve.mul(a + b, pt).unwrap(),
+                    curve
+                        .sum(curve.mul(a, pt).unwrap(), curve.mul(b, pt).unwrap())
+                        .unwrap()
+                );
+            }
         }
     }
 }
@@ -0,0 +1,251 @@
+use crate::prime::*;
+use alloc::string::{String, ToString};
+
+pub const NOT_AN_ODD_PRIME: &str = "not an odd prime number...";
+pub const DIFFERENT_FIELDS: &str = "not in the same field...";
+pub const NOT_INVERTIBLE: &str = "not invertible...";
+
+/// `a + b * sqrt(nr)` in `GF(prime^2)`, where `nr` is the smallest
+/// non-residue mod `prime`; it is found once in `new` and carried along.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gf2 {
+    a: Num,
+    b: Num,
+    prime: Num,
+    nr: Num,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtPoint {
+    Inf,
+    Affine(Gf2, Gf2),
+}
+
+impl Gf2 {
+    pub fn new(a: Num, b: Num, prime: Num) -> Result<Self, String> {
+        if prime == 2 || !prime.is_prime()? {
+            return Err(NOT_AN_ODD_PRIME.to_string());
+        }
+        Ok(Gf2 {
+            a: a.rem_euclid(prime),
+            b: b.rem_euclid(prime),
+            prime,
+            nr: Gf2::non_residue(prime)?,
+        })
+    }
+    /// Smallest `c` with `c^((prime - 1) / 2) = -1`, found by Euler's
+    /// criterion rather than by listing every residue.
+    pub fn non_residue(prime: Num) -> Result<Num, String> {
+        if prime == 2 || !prime.is_prime()? {
+            return Err(NOT_AN_ODD_PRIME.to_string());
+        }
+        for c in 2..prime {
+            if c.mod_pow((prime - 1) / 2, prime)? == prime - 1 {
+                return Ok(c);
+            }
+        }
+        Err(NOT_AN_ODD_PRIME.to_string())
+    }
+    pub fn a(&self) -> Num {
+        self.a
+    }
+    pub fn b(&self) -> Num {
+        self.b
+    }
+    pub fn prime(&self) -> Num {
+        self.prime
+    }
+    pub fn is_zero(&self) -> bool {
+        self.a == 0 && self.b == 0
+    }
+    fn check(&self, other: &Self) -> Result<(), String> {
+        if self.prime != other.prime {
+            return Err(DIFFERENT_FIELDS.to_string());
+        }
+        Ok(())
+    }
+    fn with(&self, a: Num, b: Num) -> Self {
+        Gf2 {
+            a: a.rem_euclid(self.prime),
+            b: b.rem_euclid(self.prime),
+            ..*self
+        }
+    }
+    pub fn add(&self, other: &Self) -> Result<Self, String> {
+        self.check(other)?;
+        let p = self.prime;
+        Ok(self.with(add_mod(self.a, other.a, p), add_mod(self.b, other.b, p)))
+    }
+    pub fn sub(&self, other: &Self) -> Result<Self, String> {
+        self.add(&other.neg())
+    }
+    pub fn neg(&self) -> Self {
+        self.with(-self.a, -self.b)
+    }
+    pub fn mul(&self, other: &Self) -> Result<Self, String> {
+        self.check(other)?;
+        let (p, nr) = (self.prime, self.nr);
+        let bd = mul_mod(self.b, other.b, p);
+        Ok(self.with(
+            add_mod(mul_mod(self.a, other.a, p), mul_mod(bd, nr, p), p),
+            add_mod(mul_mod(self.a, other.b, p), mul_mod(self.b, other.a, p), p),
+        ))
+    }
+    pub fn inverse(&self) -> Result<Self, String> {
+        if self.is_zero() {
+            return Err(NOT_INVERTIBLE.to_string());
+        }
+        let (p, nr) = (self.prime, self.nr);
+        let norm =
+            (mul_mod(self.a, self.a, p) - mul_mod(nr, mul_mod(self.b, self.b, p), p)).rem_euclid(p);
+        let norm_inv = norm.mod_inverse(p)?;
+        Ok(self.with(mul_mod(self.a, norm_inv, p), mul_mod(-self.b, norm_inv, p)))
+    }
+    pub fn pow(&self, exp: Num) -> Result<Self, String> {
+        if exp < 0 {
+            return Err(NOT_AN_NON_NEG.to_string());
+        }
+        let mut res = self.with(1, 0);
+        let mut base = *self;
+        let mut exp = exp;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                res = res.mul(&base)?;
+            }
+            base = base.mul(&base)?;
+            exp >>= 1;
+        }
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod test_gf2 {
+        use super::*;
+
+        #[test]
+        fn test_new() {
+            assert!(Gf2::new(1, 1, 2).is_err());
+            assert!(Gf2::new(1, 1, 9).is_err());
+            assert_eq!(Gf2::new(6, 1, 7).unwrap(), Gf2::new(-1, 8, 7).unwrap());
+            let x = Gf2::new(-1, 8, 7).unwrap();
+            assert_eq!((6, 1, 7), (x.a(), x.b(), x.prime()));
+        }
+        #[test]
+        fn test_non_residue() {
+            assert!(Gf2::non_residue(2).is_err());
+            assert_eq!(2, Gf2::non_residue(3).unwrap());
+            assert_eq!(3, Gf2::non_residue(7).unwrap());
+            assert_eq!(7, Gf2::non_residue(71).unwrap());
+            assert_eq!(5, Gf2::non_residue(1_000_000_007).unwrap());
+        }
+        #[test]
+        fn test_mul() {
+            let x = Gf2::new(2, 3, 7).unwrap();
+            let y = Gf2::new(4, 5, 7).unwrap();
+            assert_eq!(
+                Gf2::new(8 + 15 * 3, 10 + 12, 7).unwrap(),
+                x.mul(&y).unwrap()
+            );
+            assert!(x.mul(&Gf2::new(1, 1, 11).unwrap()).is_err());
+        }
+        #[test]
+        fn test_large_prime() {
+            let p = 1_000_000_007;
+            let one = Gf2::new(1, 0, p).unwrap();
+            let x = Gf2::new(p - 2, p - 3, p).unwrap();
+            let y = Gf2::new(123_456_789, p - 1, p).unwrap();
+            // nr = 5: (a + b√5)(c + d√5) = (ac + 5bd) + (ad + bc)√5, reduced by hand.
+            let xy = x.mul(&y).unwrap();
+            let ac = (p - 2) as i64 * 123_456_789 % p as i64;
+            let bd5 = (p - 3) as i64 * (p - 1) as i64 % p as i64 * 5 % p as i64;
+            let ad_bc = ((p - 2) as i64 * (p - 1) as i64 + (p - 3) as i64 * 123_456_789) % p as i64;
+            assert_eq!(
+                (((ac + bd5) % p as i64) as Num, ad_bc as Num),
+                (xy.a(), xy.b())
+            );
+            assert_eq!(one, x.mul(&x.inverse().unwrap()).unwrap());
+            assert_eq!(one, y.mul(&y.inverse().unwrap()).unwrap());
+            assert_eq!(x, x.add(&y).unwrap().sub(&y).unwrap());
+            // Frobenius: `x^p` is the conjugate `a - b√nr`.
+            assert_eq!(Gf2::new(p - 2, 3, p).unwrap(), x.pow(p).unwrap());
+        }
+        #[test]
+        fn test_inverse() {
+            let one = Gf2::new(1, 0, 7).unwrap();
+            assert!(Gf2::new(0, 0, 7).unwrap().inverse().is_err());
+            for a in 0..7 {
+                for b in 0..7 {
+                    let x = Gf2::new(a, b, 7).unwrap();
+                    if x.is_zero() {
+                        continue;
+                    }
+                    assert_eq!(one, x.mul(&x.inverse().unwrap()).unwrap());
+                }
+            }
+        }
+        fn elements(prime: Num) -> Vec<Gf2> {
+            let mut elems = Vec::new();
+            for a in 0..prime {
+                for b in 0..prime {
+                    elems.push(Gf2::new(a, b, prime).unwrap());
+                }
+            }
+            elems
+        }
+
+        #[test]
+        fn test_sub() {
+            let x = Gf2::new(2, 3, 7).unwrap();
+            let y = Gf2::new(4, 5, 7).unwrap();
+            assert_eq!(Gf2::new(-2, -2, 7).unwrap(), x.sub(&y).unwrap());
+            assert_eq!(x, x.sub(&y).unwrap().add(&y).unwrap());
+            assert!(x.sub(&x).unwrap().is_zero());
+        }
+        #[test]
+        fn test_pow() {
+            let x = Gf2::new(2, 3, 7).unwrap();
+            let one = Gf2::new(1, 0, 7).unwrap();
+            assert!(x.pow(-1).is_err());
+            assert_eq!(one, x.pow(0).unwrap());
+            assert_eq!(x, x.pow(1).unwrap());
+            assert_eq!(x.mul(&x).unwrap().mul(&x).unwrap(), x.pow(3).unwrap());
+            for x in elements(7) {
+                if !x.is_zero() {
+                    assert_eq!(one, x.pow(48).unwrap());
+                }
+            }
+        }
+        #[test]
+        fn test_field_axioms() {
+            let zero = Gf2::new(0, 0, 7).unwrap();
+            let one = Gf2::new(1, 0, 7).unwrap();
+            let elems = elements(7);
+            for x in &elems {
+                assert_eq!(*x, x.add(&zero).unwrap());
+                assert_eq!(*x, x.mul(&one).unwrap());
+                assert_eq!(zero, x.add(&x.neg()).unwrap());
+                if !x.is_zero() {
+                    assert_eq!(one, x.mul(&x.inverse().unwrap()).unwrap());
+                }
+                for y in elems.iter().step_by(3) {
+                    assert_eq!(x.add(y).unwrap(), y.add(x).unwrap());
+                    assert_eq!(x.mul(y).unwrap(), y.mul(x).unwrap());
+                    for z in elems.iter().step_by(5) {
+                        assert_eq!(
+                            x.mul(&y.add(z).unwrap()).unwrap(),
+                            x.mul(y).unwrap().add(&x.mul(z).unwrap()).unwrap()
+                        );
+                        assert_eq!(
+                            x.mul(&y.mul(z).unwrap()).unwrap(),
+                            x.mul(y).unwrap().mul(z).unwrap()
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,244 @@
+use crate::curve::*;
+use crate::ext::*;
+use crate::prime::*;
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+pub const UNSUPPORTED_DEGREE: &str = "not a supported extension degree...";
+
+/// `y^2 = x^3 + coef1 * x + coef0` over `F_{p^2}`, with both coefficients
+/// taken from the prime subfield.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExtCurve {
+    coef1: Gf2,
+    coef0: Gf2,
+    prime: Num,
+}
+
+impl EllipticCurve {
+    pub fn new_over_field(
+        coef1: Num,
+        coef0: Num,
+        prime: Num,
+        degree: u32,
+    ) -> Result<ExtCurve, String> {
+        if degree != 2 {
+            return Err(UNSUPPORTED_DEGREE.to_string());
+        }
+        let base = EllipticCurve::new(coef1, coef0, prime)?;
+        Ok(ExtCurve {
+            coef1: Gf2::new(base.coef1(), 0, prime)?,
+            coef0: Gf2::new(base.coef0(), 0, prime)?,
+            prime,
+        })
+    }
+}
+
+impl ExtCurve {
+    pub fn coef1(&self) -> Gf2 {
+        self.coef1
+    }
+    pub fn coef0(&self) -> Gf2 {
+        self.coef0
+    }
+    pub fn prime(&self) -> Num {
+        self.prime
+    }
+    /// `p^2`; `OVERFLOW` once it passes `Num::MAX`, i.e. for `p > 46337`.
+    pub fn field_size(&self) -> Result<Num, String> {
+        let p = self.prime as i64;
+        Num::try_from(p * p).map_err(|_| OVERFLOW.to_string())
+    }
+    pub fn elements(&self) -> Result<Vec<Gf2>, String> {
+        let mut elems = Vec::with_capacity(self.field_size()? as usize);
+        for a in 0..self.prime {
+            for b in 0..self.prime {
+                elems.push(Gf2::new(a, b, self.prime)?);
+            }
+        }
+        Ok(elems)
+    }
+    pub fn rhs(&self, x: Gf2) -> Result<Gf2, String> {
+        x.mul(&x)?
+            .mul(&x)?
+            .add(&self.coef1.mul(&x)?)?
+            .add(&self.coef0)
+    }
+    pub fn is_on(&self, point: ExtPoint) -> bool {
+        match point {
+            ExtPoint::Inf => true,
+            ExtPoint::Affine(x, y) => match (y.mul(&y), self.rhs(x)) {
+                (Ok(lhs), Ok(rhs)) => lhs == rhs,
+                _ => false,
+            },
+        }
+    }
+    pub fn inv(&self, point: ExtPoint) -> Result<ExtPoint, String> {
+        if !self.is_on(point) {
+            return Err(NOT_ON_THE_CURVE.to_string());
+        }
+        Ok(match point {
+            ExtPoint::Inf => ExtPoint::Inf,
+            ExtPoint::Affine(x, y) => ExtPoint::Affine(x, y.neg()),
+        })
+    }
+    pub fn sum(&self, pt0: ExtPoint, pt1: ExtPoint) -> Result<ExtPoint, String> {
+        if !self.is_on(pt0) || !self.is_on(pt1) {
+            return Err(NOT_ON_THE_CURVE.to_string());
+        }
+        let (x0, y0, x1, y1) = match (pt0, pt1) {
+            (ExtPoint::Inf, _) => return Ok(pt1),
+            (_, ExtPoint::Inf) => return Ok(pt0),
+            (ExtPoint::Affine(x0, y0), ExtPoint::Affine(x1, y1)) => (x0, y0, x1, y1),
+        };
+        if x0 == x1 && y0.add(&y1)?.is_zero() {
+            return Ok(ExtPoint::Inf);
+        }
+        let slope = if x0 == x1 {
+            let three = Gf2::new(3, 0, self.prime)?;
+            three
+                .mul(&x0)?
+                .mul(&x0)?
+                .add(&self.coef1)?
+                .mul(&y0.add(&y0)?.inverse()?)?
+        } else {
+            y1.sub(&y0)?.mul(&x1.sub(&x0)?.inverse()?)?
+        };
+        let x2 = slope.mul(&slope)?.sub(&x0)?.sub(&x1)?;
+        let y2 = slope.mul(&x0.sub(&x2)?)?.sub(&y0)?;
+        Ok(ExtPoint::Affine(x2, y2))
+    }
+    /// Counts over all of `F_{p^2}`, so it fails with `OVERFLOW` wherever
+    /// `field_size` does.
+    pub fn order(&self) -> Result<Num, String> {
+        let one = Gf2::new(1, 0, self.prime)?;
+        let half = (self.field_size()? - 1) / 2;
+        let mut ord = 1;
+        for x in self.elements()? {
+            let rhs = self.rhs(x)?;
+            if rhs.is_zero() {
+                ord += 1;
+            } else if rhs.pow(half)? == one {
+                ord += 2;
+            }
+        }
+        Ok(ord)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::group::*;
+
+    mod test_ext_curve {
+        use super::*;
+
+        fn brute_force(curve: &ExtCurve) -> Vec<ExtPoint> {
+            let elems = curve.elements().unwrap();
+            let mut points = vec![ExtPoint::Inf];
+            for x in &elems {
+                for y in &elems {
+                    if y.mul(y).unwrap() == curve.rhs(*x).unwrap() {
+                        points.push(ExtPoint::Affine(*x, *y));
+                    }
+                }
+            }
+            points
+        }
+
+        #[test]
+        fn test_new_over_field() {
+            assert!(EllipticCurve::new_over_field(1, 1, 5, 1).is_err());
+            assert!(EllipticCurve::new_over_field(1, 1, 5, 3).is_err());
+            assert!(EllipticCurve::new_over_field(1, 1, 9, 2).is_err());
+            assert!(EllipticCurve::new_over_field(1, 1, 3, 2).is_err());
+            assert!(EllipticCurve::new_over_field(0, 0, 5, 2).is_err());
+            let curve = EllipticCurve::new_over_field(-1, 6, 5, 2).unwrap();
+            assert_eq!(Gf2::new(4, 0, 5).unwrap(), curve.coef1());
+            assert_eq!(Gf2::new(1, 0, 5).unwrap(), curve.coef0());
+            assert_eq!(5, curve.prime());
+            assert_eq!(Ok(25), curve.field_size());
+        }
+        #[test]
+        fn test_field_size_overflow() {
+            let curve = EllipticCurve::new_over_field(1, 1, 46337, 2).unwrap();
+            assert_eq!(Ok(2147117569), curve.field_size());
+            let curve = EllipticCurve::new_over_field(1, 1, 46349, 2).unwrap();
+            assert_eq!(Err(OVERFLOW.to_string()), curve.field_size());
+            assert_eq!(Err(OVERFLOW.to_string()), curve.elements());
+            assert_eq!(Err(OVERFLOW.to_string()), curve.order());
+        }
+        #[test]
+        fn test_order() {
+            for prime in [5, 7] {
+                for a in 0..prime {
+                    for b in 0..prime {
+                        if (4 * a * a * a + 27 * b * b) % prime == 0 {
+                            continue;
+                        }
+                        let curve = EllipticCurve::new_over_field(a, b, prime, 2).unwrap();
+                        let ord = curve.order().unwrap();
+                        assert_eq!(brute_force(&curve).len() as Num, ord);
+                        let t = EllipticCurve::new(a, b, prime).unwrap().trace().unwrap();
+                        assert_eq!(prime * prime + 1 - (t * t - 2 * prime), ord);
+                    }
+                }
+            }
+        }
+        #[test]
+        fn test_sum() {
+            let curve = EllipticCurve::new_over_field(1, 1, 5, 2).unwrap();
+            let points = brute_force(&curve);
+            for p0 in &points {
+                assert_eq!(*p0, curve.sum(*p0, ExtPoint::Inf).unwrap());
+                assert_eq!(
+                    ExtPoint::Inf,
+                    curve.sum(*p0, curve.inv(*p0).unwrap()).unwrap()
+                );
+                for p1 in &points {
+                    let s = curve.sum(*p0, *p1).unwrap();
+                    assert!(curve.is_on(s));
+                    assert_eq!(s, curve.sum(*p1, *p0).unwrap());
+                }
+            }
+            for p0 in points.iter().step_by(3) {
+                for p1 in points.iter().step_by(2) {
+                    for p2 in points.iter().step_by(5) {
+                        assert_eq!(
+                            curve.sum(curve.sum(*p0, *p1).unwrap(), *p2).unwrap(),
+                            curve.sum(*p0, curve.sum(*p1, *p2).unwrap()).unwrap()
+                        );
+                    }
+                }
+            }
+            let off = ExtPoint::Affine(Gf2::new(0, 0, 5).unwrap(), Gf2::new(0, 0, 5).unwrap());
+            assert!(!curve.is_on(off));
+            assert!(curve.sum(off, ExtPoint::Inf).is_err());
+        }
+        #[test]
+        fn test_base_points_embed() {
+            let base = EllipticCurve::new(1, 1, 5).unwrap();
+            let curve = EllipticCurve::new_over_field(1, 1, 5, 2).unwrap();
+            let lift = |pt: Point| match pt {
+                Point::Inf => ExtPoint::Inf,
+                Point::Affine(x, y) => {
+                    ExtPoint::Affine(Gf2::new(x, 0, 5).unwrap(), Gf2::new(y, 0, 5).unwrap())
+                }
+            };
+            let sols = base.solutions().unwrap();
+            for p0 in &sols {
+                assert!(curve.is_on(lift(*p0)));
+                for p1 in &sols {
+                    assert_eq!(
+                        lift(base.sum(*p0, *p1).unwrap()),
+                        curve.sum(lift(*p0), lift(*p1)).unwrap()
+                    );
+                }
+            }
+            assert_eq!(0, curve.order().unwrap() % base.order().unwrap());
+        }
+    }
+}
@@ -1,30 +1,165 @@
 use crate::curve::*;
 use crate::prime::*;
+use crate::rng::*;
+use alloc::collections::BTreeMap;
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+pub const ANOMALOUS: &str = "an anomalous curve...";
+pub const NO_POINT_FOUND: &str = "no point found...";
+pub const NO_CYCLE: &str = "never returned to the identity...";
+pub const OUTSIDE_HASSE: &str = "outside the Hasse interval...";
+
+pub const MAX_COFACTOR: Num = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GroupStructure(pub Num, pub Num);
+
+impl core::fmt::Display for GroupStructure {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            GroupStructure(1, n) => write!(f, "Z/{}", n),
+            GroupStructure(n1, n2) => write!(f, "Z/{} x Z/{}", n1, n2),
+        }
+    }
+}
 
 pub trait Group {
     fn order(&self) -> Result<Num, String>;
+    fn order_verbose(&self) -> Result<Vec<Num>, String>;
+    fn hasse_interval(&self) -> Result<(Num, Num), String>;
+    fn order_via_twist(&self) -> Result<Num, String>;
+    /// Brute-force O(p^2) count, kept only as a reference for testing `order`.
+    fn count_points_naive(&self) -> Result<Num, String>;
     fn cyclic_group(&self, generator: Point) -> Result<Vec<Point>, String>;
+    fn subgroup(&self, generator: Point) -> Result<(Vec<Point>, bool), String>;
     fn solutions(&self) -> Result<Vec<Point>, String>;
     fn decomposition(&self) -> Result<(Num, Num), String>;
+    fn structure(&self) -> Result<GroupStructure, String>;
+    fn is_cyclic(&self) -> Result<bool, String>;
+    fn is_supersingular(&self) -> Result<bool, String>;
+    fn is_anomalous(&self) -> Result<bool, String>;
+    /// `Some(Point::Inf)` for the trivial group, which `Inf` generates.
+    fn find_generator(&self) -> Result<Option<Point>, String>;
+    fn checked_find_generator(&self) -> Result<Option<Point>, String>;
+    fn embedding_degree(&self, subgroup_order: Num) -> Result<Num, String>;
+    #[cfg(feature = "rng")]
+    fn random_point<R: Rng>(&self, rng: &mut R) -> Result<Point, String>;
+    fn nth_point(&self, n: usize) -> Result<Option<Point>, String>;
+    fn order_factors(&self) -> Result<Vec<(Num, Num)>, String>;
+    fn exponent(&self) -> Result<Num, String>;
+    /// Sorted orders that points actually take: in `Z/n1 x Z/n2` with
+    /// `n1 | n2` these are exactly the divisors of `n2`.
+    fn subgroup_orders(&self) -> Result<Vec<Num>, String>;
+    fn is_valid_public_key(&self, point: Point, subgroup_order: Num) -> Result<bool, String>;
+    fn order_bsgs(&self) -> Result<Num, String>;
+    /// Mestre's variant of Shanks: lcm of point orders on the curve and on its
+    /// twist until one group order in the Hasse interval fits both. Only
+    /// scalar multiplication is used, with products taken in `i64`, so it
+    /// works for every prime whose Hasse interval fits in a `Num`; `order`
+    /// would need `O(p)` steps and memory there.
+    fn order_fast(&self) -> Result<Num, String>;
+    fn trace(&self) -> Result<Num, String>;
+    fn has_point_of_order(&self, n: Num) -> Result<bool, String>;
+    fn point_order(&self, point: Point) -> Result<Num, String>;
+    fn generator_of_order(&self, n: Num) -> Result<Option<Point>, String>;
+    fn generators(&self) -> Result<Vec<Point>, String>;
+    /// Both the curve and its quadratic twist must have a prime-order
+    /// subgroup whose cofactor is at most `MAX_COFACTOR`.
+    fn is_twist_secure(&self, subgroup_order: Num) -> Result<bool, String>;
 }
 
-impl Group for EllipticCurve {
-    fn order(&self) -> Result<Num, String> {
-        let mut ord = 1 + self.prime;
-        for i in 0..self.prime {
-            ord += self.rhs(i)?.legendre(self.prime)?;
+/// `p + 1 ± floor(2√p)`, the range every curve over `GF(prime)` has its
+/// order in; `OVERFLOW` when the top passes `Num::MAX`.
+pub fn hasse_interval(prime: Num) -> Result<(Num, Num), String> {
+    if !prime.is_prime()? {
+        return Err(NOT_A_PRIME.to_string());
+    }
+    let p = prime as i64;
+    // `floor(2√p)` is `2 * floor(√p)` or one more; `4p` itself can pass `Num::MAX`.
+    let mut bound = 2 * prime.floor_sqrt()? as i64;
+    if (bound + 1).pow(2) <= 4 * p {
+        bound += 1;
+    }
+    let hi = Num::try_from(p + 1 + bound).map_err(|_| OVERFLOW.to_string())?;
+    Ok(((p + 1 - bound) as Num, hi))
+}
+
+impl EllipticCurve {
+    pub fn find_curve_with_order(prime: Num, order: Num) -> Result<Option<EllipticCurve>, String> {
+        let (lo, hi) = hasse_interval(prime)?;
+        if prime <= 3 {
+            return Err(SMALL_CHARACTERISTIC.to_string());
         }
-        Ok(ord)
+        if !(lo..=hi).contains(&order) {
+            return Ok(None);
+        }
+        for a in 0..prime {
+            for b in 0..prime {
+                if a == 0 && b == 0 {
+                    continue;
+                }
+                let curve = EllipticCurve::new(a, b, prime)?;
+                if curve.discriminant()? != 0 && curve.order()? == order {
+                    return Ok(Some(curve));
+                }
+            }
+        }
+        Ok(None)
     }
-    fn cyclic_group(&self, generator: Point) -> Result<Vec<Point>, String> {
-        if !self.is_on(generator) {
-            return Err(NOT_ON_THE_CURVE.to_string());
+    /// Backs `Group::random_point`; `order_bsgs` and `order_fast` use it
+    /// whether or not the `rng` feature is on.
+    pub(crate) fn sample_point<R: Rng>(&self, rng: &mut R) -> Result<Point, String> {
+        // Each miss costs one Euler test inside `sqrt_mod_prime`; building the
+        // `O(p)` `residue_set` for a handful of samples would not pay off.
+        for _ in 0..self.prime().saturating_mul(4) {
+            let x = rng.below(self.prime());
+            if let Some(y) = self.rhs(x)?.sqrt_mod_prime(self.prime())? {
+                return Ok(match rng.coin() {
+                    true => self.represent(Point::Affine(x, -y))?,
+                    false => Point::Affine(x, y),
+                });
+            }
         }
-        if generator.is_inf() {
-            return Ok(vec![Point::Inf]);
+        Err(NO_POINT_FOUND.to_string())
+    }
+    /// Some `n` in `lo..=hi` with `n * point = Inf`, by baby-step giant-step.
+    fn multiple_in_window(&self, point: Point, lo: Num, hi: Num) -> Result<Num, String> {
+        let m = (hi - lo + 1).floor_sqrt()? + 1;
+        let mut baby = BTreeMap::new();
+        for j in 0..m {
+            baby.entry(self.mul(j, point)?).or_insert(j);
         }
+        for i in 0..=m {
+            let giant = self.inv(self.mul(lo + i * m, point)?)?;
+            if let Some(j) = baby.get(&giant) {
+                if lo + i * m + j <= hi {
+                    return Ok(lo + i * m + j);
+                }
+            }
+        }
+        Err(NO_CYCLE.to_string())
+    }
+    /// Exact order of `point`, given some multiple `n` of it.
+    fn order_dividing(&self, point: Point, mut n: Num) -> Result<Num, String> {
+        for (q, _) in n.prime_factors()? {
+            while n % q == 0 && self.mul(n / q, point)?.is_inf() {
+                n /= q;
+            }
+        }
+        Ok(n)
+    }
+    /// `sum` reaching `Inf` within `cap` steps is only guaranteed for correct
+    /// arithmetic; a bug there becomes `NO_CYCLE` instead of a hang.
+    fn capped_cycle(&self, generator: Point, cap: usize) -> Result<Vec<Point>, String> {
         let mut cycle = vec![self.represent(generator)?];
         loop {
+            if cycle.len() >= cap {
+                return Err(NO_CYCLE.to_string());
+            }
             cycle.push(self.sum(generator, *cycle.last().unwrap())?);
             if cycle.last().unwrap().is_inf() {
                 break;
@@ -32,26 +167,329 @@ impl Group for EllipticCurve {
         }
         Ok(cycle)
     }
+}
+impl Group for EllipticCurve {
+    fn order(&self) -> Result<Num, String> {
+        if let Some(&ord) = self.cached_order().get() {
+            return Ok(ord);
+        }
+        // Fails with `OVERFLOW` before the `O(p)` sieve when `#E` could pass
+        // `Num::MAX`, i.e. for primes above 2147390939.
+        self.hasse_interval()?;
+        let sieve = self.residue_set()?;
+        let mut ord: i64 = 1;
+        for x in 0..self.prime() {
+            let rhs = self.rhs(x)?;
+            if rhs == 0 {
+                ord += 1;
+            } else if sieve[rhs as usize] {
+                ord += 2;
+            }
+        }
+        let ord = Num::try_from(ord).map_err(|_| OVERFLOW.to_string())?;
+        Ok(*self.cached_order().get_or_init(|| ord))
+    }
+    fn order_verbose(&self) -> Result<Vec<Num>, String> {
+        let (lo, hi) = self.hasse_interval()?;
+        let sieve = self.residue_set()?;
+        let mut sums = Vec::with_capacity(self.prime() as usize);
+        let mut ord: i64 = 1;
+        for x in 0..self.prime() {
+            let rhs = self.rhs(x)?;
+            if rhs == 0 {
+                ord += 1;
+            } else if sieve[rhs as usize] {
+                ord += 2;
+            }
+            sums.push(Num::try_from(ord).map_err(|_| OVERFLOW.to_string())?);
+        }
+        match sums.last() {
+            Some(n) if (lo..=hi).contains(n) => Ok(sums),
+            _ => Err(OUTSIDE_HASSE.to_string()),
+        }
+    }
+    fn hasse_interval(&self) -> Result<(Num, Num), String> {
+        hasse_interval(self.prime())
+    }
+    fn order_via_twist(&self) -> Result<Num, String> {
+        let twist = self.quadratic_twist()?.order()? as i64;
+        let ord = 2 * (self.prime() as i64 + 1) - twist;
+        let ord = Num::try_from(ord).map_err(|_| OVERFLOW.to_string())?;
+        debug_assert_eq!(Ok(ord), self.order());
+        Ok(ord)
+    }
+    fn count_points_naive(&self) -> Result<Num, String> {
+        let mut count = 1;
+        for x in 0..self.prime() {
+            let rhs = self.rhs(x)?;
+            for y in 0..self.prime() {
+                if self.lhs(y)? == rhs {
+                    count += 1;
+                }
+            }
+        }
+        Ok(count)
+    }
+    fn cyclic_group(&self, generator: Point) -> Result<Vec<Point>, String> {
+        if !self.is_on(generator) {
+            return Err(NOT_ON_THE_CURVE.to_string());
+        }
+        if generator.is_inf() {
+            return Ok(vec![Point::Inf]);
+        }
+        self.capped_cycle(generator, self.order()? as usize + 1)
+    }
+    fn subgroup(&self, generator: Point) -> Result<(Vec<Point>, bool), String> {
+        let cycle = self.cyclic_group(generator)?;
+        let is_full = cycle.len() as Num == self.order()?;
+        Ok((cycle, is_full))
+    }
     fn solutions(&self) -> Result<Vec<Point>, String> {
+        let sieve = self.residue_set()?;
         let mut points = vec![Point::Inf];
-        for x in 0..self.prime {
-            for y in 0..self.prime {
-                if self.lhs(y) == self.rhs(x) {
+        for x in 0..self.prime() {
+            let rhs = self.rhs(x)?;
+            if rhs != 0 && !sieve[rhs as usize] {
+                continue;
+            }
+            for y in 0..self.prime() {
+                if self.lhs(y)? == rhs {
                     points.push(Point::Affine(x, y));
                 }
             }
         }
+        debug_assert!(points.windows(2).all(|w| w[0] < w[1]));
+        debug_assert_eq!(Ok(points.len() as Num), self.order());
         Ok(points)
     }
     fn decomposition(&self) -> Result<(Num, Num), String> {
-        let mut max_len = 0;
+        let order = self.order()?;
+        // Some point reaches the exponent `n2`; a generator ends the scan early.
+        let mut exponent = 1;
+        for x in 0..self.prime() {
+            for pt in self.points_with_x(x)? {
+                exponent = exponent.max(self.order_dividing(pt, order)?);
+                if exponent == order {
+                    return Ok((1, order));
+                }
+            }
+        }
+        Ok((order / exponent, exponent))
+    }
+    fn structure(&self) -> Result<GroupStructure, String> {
+        let (n1, n2) = self.decomposition()?;
+        Ok(GroupStructure(n1, n2))
+    }
+    fn is_cyclic(&self) -> Result<bool, String> {
+        Ok(self.decomposition()?.0 == 1)
+    }
+    fn is_supersingular(&self) -> Result<bool, String> {
+        Ok(self.order()? == self.prime() + 1)
+    }
+    fn is_anomalous(&self) -> Result<bool, String> {
+        Ok(self.order()? == self.prime())
+    }
+    fn find_generator(&self) -> Result<Option<Point>, String> {
+        let ord = self.order()?;
         for sol in self.solutions()? {
-            let len = self.cyclic_group(sol)?.len();
-            if len > max_len {
-                max_len = len;
+            if self.cyclic_group(sol)?.len() as Num == ord {
+                return Ok(Some(sol));
             }
         }
-        Ok((self.order()? / max_len as Num, max_len as Num))
+        Ok(None)
+    }
+    fn checked_find_generator(&self) -> Result<Option<Point>, String> {
+        if self.is_anomalous()? {
+            return Err(ANOMALOUS.to_string());
+        }
+        self.find_generator()
+    }
+    fn embedding_degree(&self, subgroup_order: Num) -> Result<Num, String> {
+        self.prime().mult_order(subgroup_order)
+    }
+    #[cfg(feature = "rng")]
+    fn random_point<R: Rng>(&self, rng: &mut R) -> Result<Point, String> {
+        self.sample_point(rng)
+    }
+    fn nth_point(&self, n: usize) -> Result<Option<Point>, String> {
+        if n == 0 {
+            return Ok(Some(Point::Inf));
+        }
+        let sieve = self.residue_set()?;
+        let mut rest = n - 1;
+        for x in 0..self.prime() {
+            let rhs = self.rhs(x)?;
+            let count = match rhs {
+                0 => 1,
+                r if sieve[r as usize] => 2,
+                _ => continue,
+            };
+            if rest < count {
+                return Ok(Some(Point::Affine(
+                    x,
+                    rhs.sqrts_mod_prime(self.prime())?[rest],
+                )));
+            }
+            rest -= count;
+        }
+        Ok(None)
+    }
+    fn order_factors(&self) -> Result<Vec<(Num, Num)>, String> {
+        if let Some(factors) = self.cached_order_factors().get() {
+            return Ok(factors.clone());
+        }
+        let factors = self.order()?.prime_factors()?;
+        Ok(self.cached_order_factors().get_or_init(|| factors).clone())
+    }
+    fn exponent(&self) -> Result<Num, String> {
+        Ok(self.decomposition()?.1)
+    }
+    fn subgroup_orders(&self) -> Result<Vec<Num>, String> {
+        self.decomposition()?.1.divisors()
+    }
+    fn is_valid_public_key(&self, point: Point, subgroup_order: Num) -> Result<bool, String> {
+        if subgroup_order < 1 {
+            return Err(NOT_A_POS.to_string());
+        }
+        if point.is_inf() || !self.is_on(point) {
+            return Ok(false);
+        }
+        Ok(self.mul(subgroup_order, point)?.is_inf())
+    }
+    fn order_bsgs(&self) -> Result<Num, String> {
+        let p = self.prime();
+        let (lo, hi) = self.hasse_interval()?;
+        let bound = hi - p - 1;
+        let m = (2 * bound).floor_sqrt()? + 1;
+        let mut candidates: Vec<Num> = (lo..=hi).collect();
+        let mut rng = XorShift::new(p as u32);
+        for _ in 0..20 {
+            if candidates.len() <= 1 {
+                break;
+            }
+            let pt = match self.sample_point(&mut rng) {
+                Ok(pt) => pt,
+                Err(_) => break,
+            };
+            let mut baby: BTreeMap<Point, Vec<Num>> = BTreeMap::new();
+            let mut step = Point::Inf;
+            for j in 0..m {
+                baby.entry(step).or_default().push(j);
+                step = self.sum(step, pt)?;
+            }
+            let giant = self.inv(step)?;
+            let mut acc = self.mul(p + 1 + bound, pt)?;
+            let mut found = Vec::new();
+            for i in 0..=m {
+                for j in baby.get(&acc).into_iter().flatten() {
+                    let t = -bound + i * m + j;
+                    if t <= bound {
+                        found.push(p + 1 - t);
+                    }
+                }
+                acc = self.sum(acc, giant)?;
+            }
+            candidates.retain(|n| found.contains(n));
+        }
+        match candidates[..] {
+            [n] => Ok(n),
+            _ => self.order(),
+        }
+    }
+    fn order_fast(&self) -> Result<Num, String> {
+        let p = self.prime();
+        // Mestre's theorem only guarantees a unique fit for p > 229.
+        if p <= 229 {
+            return self.order();
+        }
+        let (lo, hi) = self.hasse_interval()?;
+        let twist = self.quadratic_twist()?;
+        let mut rng = XorShift::new(p as u32);
+        let (mut lcm, mut twist_lcm) = (1, 1);
+        for round in 0..64 {
+            let (curve, acc) = match round % 2 {
+                0 => (self, &mut lcm),
+                _ => (&twist, &mut twist_lcm),
+            };
+            let pt = curve.sample_point(&mut rng)?;
+            let n = curve.multiple_in_window(pt, lo, hi)?;
+            let ord = curve.order_dividing(pt, n)?;
+            *acc = *acc / acc.gcd(ord) * ord;
+            let mut candidates = (lo..=hi).filter(|&n| {
+                n % lcm == 0 && (2 * (p as i64 + 1) - n as i64) % twist_lcm as i64 == 0
+            });
+            if let (Some(n), None) = (candidates.next(), candidates.next()) {
+                return Ok(n);
+            }
+        }
+        self.order()
+    }
+    fn trace(&self) -> Result<Num, String> {
+        let n = self.order()?;
+        Num::try_from(self.prime() as i64 + 1 - n as i64).map_err(|_| OVERFLOW.to_string())
+    }
+    fn has_point_of_order(&self, n: Num) -> Result<bool, String> {
+        let factors = self.order_factors()?;
+        for (p, e) in n.prime_factors()? {
+            if !factors.iter().any(|&(q, f)| q == p && f >= e) {
+                return Ok(false);
+            }
+        }
+        Ok(self.exponent()?.rem_euclid(n) == 0)
+    }
+    fn point_order(&self, point: Point) -> Result<Num, String> {
+        if !self.is_on(point) {
+            return Err(NOT_ON_THE_CURVE.to_string());
+        }
+        for d in self.order()?.divisors()? {
+            if self.mul(d, point)?.is_inf() {
+                return Ok(d);
+            }
+        }
+        Err(NO_CYCLE.to_string())
+    }
+    fn generator_of_order(&self, n: Num) -> Result<Option<Point>, String> {
+        if n < 1 {
+            return Err(NOT_A_POS.to_string());
+        }
+        let ord = self.order()?;
+        if ord.rem_euclid(n) != 0 {
+            return Ok(None);
+        }
+        let gene = match self.find_generator()? {
+            Some(gene) => self.mul(ord / n, gene)?,
+            None => return Ok(None),
+        };
+        match self.point_order(gene)? == n {
+            true => Ok(Some(gene)),
+            false => Ok(None),
+        }
+    }
+    fn generators(&self) -> Result<Vec<Point>, String> {
+        let gene = match self.find_generator()? {
+            Some(gene) => gene,
+            None => return Ok(vec![]),
+        };
+        let ord = self.order()?;
+        let mut genes = Vec::new();
+        for k in 1..=ord {
+            if k.gcd(ord) == 1 {
+                genes.push(self.mul(k, gene)?);
+            }
+        }
+        Ok(genes)
+    }
+    fn is_twist_secure(&self, subgroup_order: Num) -> Result<bool, String> {
+        if !subgroup_order.is_prime()? {
+            return Err(NOT_A_PRIME.to_string());
+        }
+        let ord = self.order()?;
+        if ord.rem_euclid(subgroup_order) != 0 || ord / subgroup_order > MAX_COFACTOR {
+            return Ok(false);
+        }
+        let twist = self.quadratic_twist()?.order()?;
+        let largest = twist.prime_factors()?.last().map_or(1, |&(q, _)| q);
+        Ok(twist / largest <= MAX_COFACTOR)
     }
 }
 
@@ -72,12 +510,185 @@ mod test {
             assert_eq!(220, curve.order().unwrap());
         }
         #[test]
+        fn test_order_largest_prime() {
+            // Well past the old `i32` product limit of 46340. `order` is `O(p)`,
+            // so the 2147390939 limit is left to `order_fast` and `OVERFLOW` below.
+            let prime = 1_000_003;
+            for (a, b) in [(-1, 0), (0, 7), (3, 11)] {
+                let curve = EllipticCurve::new(a, b, prime).unwrap();
+                let ord = curve.order().unwrap();
+                let (lo, hi) = curve.hasse_interval().unwrap();
+                assert!((lo..=hi).contains(&ord));
+                assert_eq!(ord, curve.order_fast().unwrap());
+                assert_eq!(ord, *curve.order_verbose().unwrap().last().unwrap());
+            }
+            for (a, b) in [(-1, 0), (57, 97), (5, 1)] {
+                let curve = EllipticCurve::new(a, b, 1009).unwrap();
+                assert_eq!(curve.count_points_naive(), curve.order());
+            }
+            let curve = EllipticCurve::new(-1, 0, Num::MAX).unwrap();
+            assert_eq!(Err(OVERFLOW.to_string()), curve.order());
+            assert_eq!(Err(OVERFLOW.to_string()), curve.order_verbose());
+        }
+        #[test]
+        fn test_order_verbose() {
+            for (a, b, p) in [(-1, 0, 71), (1, 6, 11), (57, 97, 199), (3, 11, 53)] {
+                let curve = EllipticCurve::new(a, b, p).unwrap();
+                let sums = curve.order_verbose().unwrap();
+                assert_eq!(p as usize, sums.len());
+                assert!(sums.windows(2).all(|w| w[0] <= w[1]));
+                assert_eq!(curve.order().unwrap(), *sums.last().unwrap());
+            }
+        }
+        #[test]
+        fn test_hasse_interval() {
+            assert_eq!(
+                (56, 88),
+                EllipticCurve::new(-1, 0, 71)
+                    .unwrap()
+                    .hasse_interval()
+                    .unwrap()
+            );
+            assert_eq!(
+                (6, 18),
+                EllipticCurve::new(1, 6, 11)
+                    .unwrap()
+                    .hasse_interval()
+                    .unwrap()
+            );
+            let prime = 53;
+            for a in 0..prime {
+                for b in 0..prime {
+                    if a == 0 && b == 0 {
+                        continue;
+                    }
+                    let curve = EllipticCurve::new(a, b, prime).unwrap();
+                    let (lo, hi) = curve.hasse_interval().unwrap();
+                    assert!((lo..=hi).contains(&curve.order().unwrap()));
+                }
+            }
+        }
+        #[test]
+        fn test_order_via_twist() {
+            let curve = EllipticCurve::new(-1, 0, 71).unwrap();
+            assert_eq!(72, curve.order_via_twist().unwrap());
+            let curve = EllipticCurve::new(57, 97, 199).unwrap();
+            assert_eq!(220, curve.order_via_twist().unwrap());
+            for prime in [5, 11, 23, 53] {
+                for a in 0..prime {
+                    for b in 0..prime {
+                        if (4 * a * a * a + 27 * b * b) % prime == 0 {
+                            continue;
+                        }
+                        let curve = EllipticCurve::new(a, b, prime).unwrap();
+                        assert_eq!(curve.order().unwrap(), curve.order_via_twist().unwrap());
+                    }
+                }
+            }
+            let curve = EllipticCurve::new(2, 3, 50021).unwrap();
+            assert_eq!(49776, curve.order_via_twist().unwrap());
+            // `2 * (p + 1)` passes `Num::MAX` here; the twist's order fails first.
+            let curve = EllipticCurve::new(-1, 0, Num::MAX).unwrap();
+            assert_eq!(Err(OVERFLOW.to_string()), curve.order_via_twist());
+        }
+        #[test]
+        fn test_count_points_naive() {
+            let prime = 23;
+            for a in 0..prime {
+                for b in 0..prime {
+                    if a == 0 && b == 0 {
+                        continue;
+                    }
+                    let curve = EllipticCurve::new(a, b, prime).unwrap();
+                    assert_eq!(curve.order().unwrap(), curve.count_points_naive().unwrap());
+                }
+            }
+            let curve = EllipticCurve::new(-1, 0, 71).unwrap();
+            assert_eq!(72, curve.count_points_naive().unwrap());
+        }
+        #[test]
+        fn test_order_matches_legendre() {
+            for prime in [5, 11, 23, 53] {
+                for a in 0..prime {
+                    for b in 0..prime {
+                        if a == 0 && b == 0 {
+                            continue;
+                        }
+                        let curve = EllipticCurve::new(a, b, prime).unwrap();
+                        let mut ord = 1 + prime;
+                        for x in 0..prime {
+                            ord += curve.rhs(x).unwrap().legendre(prime).unwrap();
+                        }
+                        assert_eq!(ord, curve.order().unwrap());
+                    }
+                }
+            }
+        }
+        #[test]
         fn test_cyclic_group() {
             let curve = EllipticCurve::new(3, 11, 53).unwrap();
             assert_eq!(57, curve.cyclic_group(Point::Affine(9, 5)).unwrap().len());
             assert_eq!(19, curve.cyclic_group(Point::Affine(38, 47)).unwrap().len());
         }
         #[test]
+        fn test_find_curve_with_order() {
+            let curve = EllipticCurve::find_curve_with_order(53, 53)
+                .unwrap()
+                .unwrap();
+            assert_eq!(53, curve.order().unwrap());
+            assert!(curve.is_anomalous().unwrap());
+            let curve = EllipticCurve::find_curve_with_order(71, 67)
+                .unwrap()
+                .unwrap();
+            assert_eq!(67, curve.order().unwrap());
+            assert_ne!(0, curve.discriminant().unwrap());
+            assert_eq!(vec![(67, 1)], curve.order_factors().unwrap());
+            for n in 6..=18 {
+                let curve = EllipticCurve::find_curve_with_order(11, n)
+                    .unwrap()
+                    .unwrap();
+                assert_eq!(n, curve.order().unwrap());
+            }
+            assert_eq!(None, EllipticCurve::find_curve_with_order(11, 5).unwrap());
+            assert_eq!(None, EllipticCurve::find_curve_with_order(11, 19).unwrap());
+            assert_eq!(
+                Err(NOT_A_PRIME.to_string()),
+                EllipticCurve::find_curve_with_order(12, 13)
+            );
+            for (prime, order) in [(2, 4), (3, 4), (3, 100)] {
+                assert_eq!(
+                    Err(SMALL_CHARACTERISTIC.to_string()),
+                    EllipticCurve::find_curve_with_order(prime, order)
+                );
+            }
+        }
+        #[test]
+        fn test_hasse_interval_of_prime() {
+            assert_eq!(Ok((1, 5)), hasse_interval(2));
+            assert_eq!(Ok((1, 7)), hasse_interval(3));
+            assert_eq!(Ok((56, 88)), hasse_interval(71));
+            assert_eq!(Ok((2147298261, 2147483619)), hasse_interval(2_147_390_939));
+            assert_eq!(Err(OVERFLOW.to_string()), hasse_interval(Num::MAX));
+            assert_eq!(Err(NOT_A_PRIME.to_string()), hasse_interval(9));
+            assert!(hasse_interval(-7).is_err());
+        }
+        #[test]
+        fn test_capped_cycle() {
+            let curve = EllipticCurve::new(3, 11, 53).unwrap();
+            let pt = Point::Affine(38, 47);
+            assert_eq!(19, curve.capped_cycle(pt, 19).unwrap().len());
+            assert_eq!(
+                curve.cyclic_group(pt).unwrap(),
+                curve.capped_cycle(pt, 58).unwrap()
+            );
+            assert_eq!(Err(NO_CYCLE.to_string()), curve.capped_cycle(pt, 18));
+            assert_eq!(Err(NO_CYCLE.to_string()), curve.capped_cycle(pt, 1));
+            assert_eq!(
+                57,
+                curve.capped_cycle(Point::Affine(9, 5), 57).unwrap().len()
+            );
+        }
+        #[test]
         fn test_solutions() {
             let prime = 53;
             for a in 0..prime {
@@ -93,5 +704,407 @@ mod test {
                 }
             }
         }
+        #[test]
+        fn test_solutions_two_torsion() {
+            let curve = EllipticCurve::new(-1, 0, 71).unwrap();
+            let sols = curve.solutions().unwrap();
+            let with_zero_y: Vec<Point> = sols
+                .iter()
+                .copied()
+                .filter(|pt| pt.y() == Some(0))
+                .collect();
+            assert_eq!(
+                vec![
+                    Point::Affine(0, 0),
+                    Point::Affine(1, 0),
+                    Point::Affine(70, 0)
+                ],
+                with_zero_y
+            );
+            let mut dedup = sols.clone();
+            dedup.dedup();
+            assert_eq!(sols, dedup);
+            assert_eq!(curve.order().unwrap(), sols.len() as Num);
+            let curve = EllipticCurve::new(3, 11, 53).unwrap();
+            assert!(curve
+                .solutions()
+                .unwrap()
+                .iter()
+                .all(|pt| pt.y() != Some(0)));
+        }
+        #[test]
+        fn test_is_supersingular() {
+            let curve = EllipticCurve::new(-1, 0, 71).unwrap();
+            assert!(curve.is_supersingular().unwrap());
+            let curve = EllipticCurve::new(0, 1, 47).unwrap();
+            assert!(curve.is_supersingular().unwrap());
+            let curve = EllipticCurve::new(1, 6, 11).unwrap();
+            assert!(!curve.is_supersingular().unwrap());
+            let curve = EllipticCurve::new(57, 97, 199).unwrap();
+            assert!(!curve.is_supersingular().unwrap());
+        }
+        #[test]
+        fn test_is_anomalous() {
+            let curve = EllipticCurve::new(1, 39, 47).unwrap();
+            assert_eq!(47, curve.order().unwrap());
+            assert!(curve.is_anomalous().unwrap());
+            assert!(curve.find_generator().unwrap().is_some());
+            assert!(curve.checked_find_generator().is_err());
+            let curve = EllipticCurve::new(1, 6, 11).unwrap();
+            assert!(!curve.is_anomalous().unwrap());
+            assert!(curve.checked_find_generator().unwrap().is_some());
+        }
+        #[test]
+        fn test_find_generator() {
+            let curve = EllipticCurve::new(3, 11, 53).unwrap();
+            let gene = curve.find_generator().unwrap().unwrap();
+            assert_eq!(
+                curve.order().unwrap(),
+                curve.cyclic_group(gene).unwrap().len() as Num
+            );
+            let curve = EllipticCurve::new(-1, 0, 71).unwrap();
+            assert_eq!(None, curve.find_generator().unwrap());
+        }
+        #[test]
+        fn test_embedding_degree() {
+            let curve = EllipticCurve::new(-1, 0, 71).unwrap();
+            assert_eq!(2, curve.embedding_degree(3).unwrap());
+            let curve = EllipticCurve::new(0, 1, 47).unwrap();
+            assert_eq!(2, curve.embedding_degree(3).unwrap());
+            let curve = EllipticCurve::new(1, 6, 11).unwrap();
+            assert_eq!(12, curve.embedding_degree(13).unwrap());
+            assert!(curve.embedding_degree(12).is_err());
+        }
+        #[cfg(feature = "rng")]
+        #[test]
+        fn test_random_point() {
+            let mut rng = XorShift::new(2024);
+            let curve = EllipticCurve::new(3, 11, 53).unwrap();
+            for _ in 0..100 {
+                let pt = curve.random_point(&mut rng).unwrap();
+                assert!(!pt.is_inf());
+                assert!(curve.is_on(pt));
+                assert_eq!(pt, curve.represent(pt).unwrap());
+            }
+        }
+        #[test]
+        fn test_nth_point() {
+            for (a, b, p) in [(7, 5, 13), (-1, 0, 71), (3, 11, 53)] {
+                let curve = EllipticCurve::new(a, b, p).unwrap();
+                assert_eq!(Some(Point::Inf), curve.nth_point(0).unwrap());
+                let sols = curve.solutions().unwrap();
+                for (i, sol) in sols.iter().enumerate() {
+                    assert_eq!(Some(*sol), curve.nth_point(i).unwrap());
+                }
+                assert_eq!(None, curve.nth_point(sols.len()).unwrap());
+                assert_eq!(None, curve.nth_point(sols.len() + 10).unwrap());
+            }
+        }
+        #[test]
+        fn test_order_factors() {
+            let mut curve = EllipticCurve::new(-1, 0, 71).unwrap();
+            assert_eq!(None, curve.cached_order_factors().get());
+            assert_eq!(vec![(2, 3), (3, 2)], curve.order_factors().unwrap());
+            assert_eq!(Some(&72), curve.cached_order().get());
+            assert_eq!(vec![(2, 3), (3, 2)], curve.order_factors().unwrap());
+            curve.set_coef0(1).unwrap();
+            assert_eq!(
+                (None, None),
+                (
+                    curve.cached_order().get(),
+                    curve.cached_order_factors().get()
+                )
+            );
+            let fresh = EllipticCurve::new(-1, 1, 71).unwrap();
+            assert_eq!(fresh.order(), curve.order());
+            assert_eq!(fresh.order_factors(), curve.order_factors());
+            let curve = EllipticCurve::new(1, 6, 11).unwrap();
+            assert_eq!(vec![(13, 1)], curve.order_factors().unwrap());
+            let curve = EllipticCurve::new(57, 97, 199).unwrap();
+            assert_eq!(
+                vec![(2, 2), (5, 1), (11, 1)],
+                curve.order_factors().unwrap()
+            );
+        }
+        #[test]
+        fn test_exponent() {
+            for (a, b, p) in [(-1, 0, 71), (1, 6, 11), (3, 11, 53), (-1, 0, 47)] {
+                let curve = EllipticCurve::new(a, b, p).unwrap();
+                assert_eq!(curve.decomposition().unwrap().1, curve.exponent().unwrap());
+            }
+            let curve = EllipticCurve::new(-1, 0, 71).unwrap();
+            assert_eq!(36, curve.exponent().unwrap());
+        }
+        #[test]
+        fn test_subgroup_orders() {
+            for (a, b, p) in [
+                (-1, 0, 71),
+                (1, 6, 11),
+                (3, 11, 53),
+                (-1, 0, 47),
+                (2, 3, 97),
+            ] {
+                let curve = EllipticCurve::new(a, b, p).unwrap();
+                let mut orders: Vec<Num> = curve
+                    .solutions()
+                    .unwrap()
+                    .iter()
+                    .map(|pt| curve.point_order(*pt).unwrap())
+                    .collect();
+                orders.sort();
+                orders.dedup();
+                assert_eq!(orders, curve.subgroup_orders().unwrap());
+            }
+            let curve = EllipticCurve::new(-1, 0, 71).unwrap();
+            assert_eq!(
+                vec![1, 2, 3, 4, 6, 9, 12, 18, 36],
+                curve.subgroup_orders().unwrap()
+            );
+            // Prime order 49663 > 46340 over GF(50021).
+            let curve = EllipticCurve::new(1, 29, 50021).unwrap();
+            assert_eq!(vec![1, 49663], curve.subgroup_orders().unwrap());
+            let curve = EllipticCurve::new(2, 3, 50021).unwrap();
+            let orders = curve.subgroup_orders().unwrap();
+            assert_eq!(Some(&curve.exponent().unwrap()), orders.last());
+            assert!(orders.iter().all(|n| 49776 % n == 0));
+        }
+        #[test]
+        fn test_is_valid_public_key() {
+            let curve = EllipticCurve::new(3, 11, 53).unwrap();
+            assert!(curve
+                .is_valid_public_key(Point::Affine(38, 47), 19)
+                .unwrap());
+            assert!(curve.is_valid_public_key(Point::Affine(9, 5), 57).unwrap());
+            assert!(!curve.is_valid_public_key(Point::Affine(9, 5), 19).unwrap());
+            assert!(!curve.is_valid_public_key(Point::Affine(9, 6), 19).unwrap());
+            assert!(!curve.is_valid_public_key(Point::Inf, 19).unwrap());
+            assert!(curve.is_valid_public_key(Point::Affine(38, 47), 0).is_err());
+        }
+        #[test]
+        fn test_order_bsgs() {
+            let curve = EllipticCurve::new(-1, 0, 71).unwrap();
+            assert_eq!(72, curve.order_bsgs().unwrap());
+            let curve = EllipticCurve::new(1, 6, 11).unwrap();
+            assert_eq!(13, curve.order_bsgs().unwrap());
+            let curve = EllipticCurve::new(57, 97, 199).unwrap();
+            assert_eq!(220, curve.order_bsgs().unwrap());
+            for prime in [5, 13, 23] {
+                for a in 0..prime {
+                    for b in 0..prime {
+                        if (4 * a * a * a + 27 * b * b) % prime == 0 {
+                            continue;
+                        }
+                        let curve = EllipticCurve::new(a, b, prime).unwrap();
+                        assert_eq!(curve.order().unwrap(), curve.order_bsgs().unwrap());
+                    }
+                }
+            }
+        }
+        #[test]
+        fn test_order_fast() {
+            for prime in [13, 229, 233] {
+                for a in 0..prime {
+                    for b in (0..prime).step_by(7) {
+                        if (4 * a * a * a + 27 * b * b) % prime == 0 {
+                            continue;
+                        }
+                        let curve = EllipticCurve::new(a, b, prime).unwrap();
+                        assert_eq!(curve.order().unwrap(), curve.order_fast().unwrap());
+                    }
+                }
+            }
+            for prime in [1009, 10007, 23159, 39989, 40009, 50021, 65537] {
+                for (a, b) in [(-1, 0), (0, 7), (1, 1), (2, 3), (3, 11), (57, 97)] {
+                    let curve = EllipticCurve::new(a, b, prime).unwrap();
+                    assert_eq!(curve.order().unwrap(), curve.order_fast().unwrap());
+                }
+            }
+        }
+        #[test]
+        fn test_order_fast_large_prime() {
+            // `x^3 - x` is supersingular for `p = 3 (mod 4)`, so `#E = p + 1`.
+            for prime in [1_000_000_007, 2_147_390_939] {
+                let curve = EllipticCurve::new(-1, 0, prime).unwrap();
+                assert_eq!(prime + 1, curve.order_fast().unwrap());
+            }
+            let curve = EllipticCurve::new(2, 3, 1_000_000_007).unwrap();
+            let n = curve.order_fast().unwrap();
+            let twist = curve.quadratic_twist().unwrap();
+            assert_eq!(2 * (1_000_000_007 + 1) - n, twist.order_fast().unwrap());
+            let mut rng = XorShift::new(7);
+            for _ in 0..8 {
+                let pt = curve.sample_point(&mut rng).unwrap();
+                assert!(curve.mul(n, pt).unwrap().is_inf());
+            }
+            // The largest prime, `Num::MAX` itself, has a Hasse interval past it.
+            assert_eq!(
+                Err(OVERFLOW.to_string()),
+                EllipticCurve::new(-1, 0, Num::MAX).unwrap().order_fast()
+            );
+        }
+        #[test]
+        fn test_trace() {
+            let curve = EllipticCurve::new(-1, 0, 71).unwrap();
+            assert_eq!(0, curve.trace().unwrap());
+            let curve = EllipticCurve::new(1, 6, 11).unwrap();
+            assert_eq!(-1, curve.trace().unwrap());
+            let curve = EllipticCurve::new(57, 97, 199).unwrap();
+            assert_eq!(-20, curve.trace().unwrap());
+            let prime = 53;
+            for a in 0..prime {
+                for b in 0..prime {
+                    if (4 * a * a * a + 27 * b * b) % prime == 0 {
+                        continue;
+                    }
+                    let t = EllipticCurve::new(a, b, prime).unwrap().trace().unwrap();
+                    assert!(t.abs() <= (4 * prime).floor_sqrt().unwrap());
+                }
+            }
+            let curve = EllipticCurve::new(2, 3, 50021).unwrap();
+            assert_eq!(50022 - 49776, curve.trace().unwrap());
+            let curve = EllipticCurve::new(-1, 0, Num::MAX).unwrap();
+            assert_eq!(Err(OVERFLOW.to_string()), curve.trace());
+        }
+        #[test]
+        fn test_has_point_of_order() {
+            let curve = EllipticCurve::new(-1, 0, 71).unwrap();
+            assert!(curve.has_point_of_order(0).is_err());
+            assert!(curve.has_point_of_order(-2).is_err());
+            let orders: Vec<Num> = curve
+                .solutions()
+                .unwrap()
+                .into_iter()
+                .map(|pt| curve.cyclic_group(pt).unwrap().len() as Num)
+                .collect();
+            for n in 1..=80 {
+                assert_eq!(orders.contains(&n), curve.has_point_of_order(n).unwrap());
+            }
+            assert!(curve.has_point_of_order(36).unwrap());
+            assert!(!curve.has_point_of_order(8).unwrap());
+            assert!(!curve.has_point_of_order(72).unwrap());
+        }
+        #[test]
+        fn test_point_order() {
+            let curve = EllipticCurve::new(3, 11, 53).unwrap();
+            assert_eq!(1, curve.point_order(Point::Inf).unwrap());
+            assert_eq!(57, curve.point_order(Point::Affine(9, 5)).unwrap());
+            assert_eq!(19, curve.point_order(Point::Affine(38, 47)).unwrap());
+            assert!(curve.point_order(Point::Affine(9, 6)).is_err());
+            // #E = 49663 is prime, so `divisors` meets a factor above 46340.
+            let curve = EllipticCurve::new(1, 29, 50021).unwrap();
+            assert_eq!(49663, curve.order().unwrap());
+            for x in 0..20 {
+                for pt in curve.points_with_x(x).unwrap() {
+                    assert_eq!(49663, curve.point_order(pt).unwrap());
+                }
+            }
+            let curve = EllipticCurve::new(2, 3, 50021).unwrap();
+            let mut rng = XorShift::new(50021);
+            for _ in 0..8 {
+                let pt = curve.sample_point(&mut rng).unwrap();
+                let n = curve.point_order(pt).unwrap();
+                assert_eq!(0, 49776 % n);
+                assert!(curve.mul(n, pt).unwrap().is_inf());
+            }
+        }
+        #[test]
+        fn test_point_order_matches_cycle() {
+            for (a, b, p) in [(3, 11, 53), (-1, 0, 71), (57, 97, 199), (1, 6, 11)] {
+                let curve = EllipticCurve::new(a, b, p).unwrap();
+                for pt in curve.solutions().unwrap() {
+                    let cycle = curve.cyclic_group(pt).unwrap();
+                    let mut linear = 1;
+                    let mut acc = pt;
+                    while !acc.is_inf() {
+                        acc = curve.sum(acc, pt).unwrap();
+                        linear += 1;
+                    }
+                    assert_eq!(cycle.len() as Num, curve.point_order(pt).unwrap());
+                    assert_eq!(linear, curve.point_order(pt).unwrap());
+                }
+            }
+        }
+        #[test]
+        fn test_generator_of_order() {
+            let curve = EllipticCurve::new(3, 11, 53).unwrap();
+            assert!(curve.generator_of_order(0).is_err());
+            for n in [1, 3, 19, 57] {
+                let gene = curve.generator_of_order(n).unwrap().unwrap();
+                assert_eq!(n, curve.point_order(gene).unwrap());
+            }
+            assert_eq!(None, curve.generator_of_order(5).unwrap());
+            let curve = EllipticCurve::new(-1, 0, 71).unwrap();
+            assert_eq!(None, curve.generator_of_order(3).unwrap());
+        }
+        #[test]
+        fn test_structure() {
+            assert_eq!("Z/72", GroupStructure(1, 72).to_string());
+            assert_eq!("Z/2 x Z/36", GroupStructure(2, 36).to_string());
+            let curve = EllipticCurve::new(3, 11, 53).unwrap();
+            assert_eq!(GroupStructure(1, 57), curve.structure().unwrap());
+            assert_eq!("Z/57", curve.structure().unwrap().to_string());
+            let curve = EllipticCurve::new(-1, 0, 71).unwrap();
+            assert_eq!("Z/2 x Z/36", curve.structure().unwrap().to_string());
+        }
+        #[test]
+        fn test_is_cyclic() {
+            assert!(EllipticCurve::new(3, 11, 53).unwrap().is_cyclic().unwrap());
+            assert!(EllipticCurve::new(1, 6, 11).unwrap().is_cyclic().unwrap());
+            assert!(!EllipticCurve::new(-1, 0, 71).unwrap().is_cyclic().unwrap());
+            assert!(!EllipticCurve::new(-1, 0, 47).unwrap().is_cyclic().unwrap());
+        }
+        #[test]
+        fn test_subgroup() {
+            let curve = EllipticCurve::new(3, 11, 53).unwrap();
+            let (cycle, is_full) = curve.subgroup(Point::Affine(9, 5)).unwrap();
+            assert_eq!(57, cycle.len());
+            assert!(is_full);
+            let (cycle, is_full) = curve.subgroup(Point::Affine(38, 47)).unwrap();
+            assert_eq!(19, cycle.len());
+            assert!(!is_full);
+            let (cycle, is_full) = curve.subgroup(Point::Inf).unwrap();
+            assert_eq!(vec![Point::Inf], cycle);
+            assert!(!is_full);
+            assert!(curve.subgroup(Point::Affine(9, 6)).is_err());
+        }
+        #[test]
+        fn test_generators() {
+            for (a, b, p) in [(3, 11, 53), (1, 6, 11), (2, 2, 53)] {
+                let curve = EllipticCurve::new(a, b, p).unwrap();
+                let ord = curve.order().unwrap();
+                let genes = curve.generators().unwrap();
+                assert_eq!(ord.euler_phi().unwrap(), genes.len() as Num);
+                for gene in &genes {
+                    assert_eq!(ord, curve.point_order(*gene).unwrap());
+                }
+                let expected: Vec<Point> = curve
+                    .solutions()
+                    .unwrap()
+                    .into_iter()
+                    .filter(|pt| curve.point_order(*pt).unwrap() == ord)
+                    .collect();
+                let mut sorted = genes.clone();
+                sorted.sort();
+                assert_eq!(expected, sorted);
+            }
+            let curve = EllipticCurve::new(-1, 0, 71).unwrap();
+            assert!(curve.generators().unwrap().is_empty());
+        }
+        #[test]
+        fn test_is_twist_secure() {
+            let curve = EllipticCurve::new(2, 2, 53).unwrap();
+            assert_eq!(46, curve.order().unwrap());
+            assert_eq!(62, curve.quadratic_twist().unwrap().order().unwrap());
+            assert!(curve.is_twist_secure(23).unwrap());
+            assert!(!curve.is_twist_secure(2).unwrap());
+            assert!(!curve.is_twist_secure(19).unwrap());
+            assert!(curve.is_twist_secure(46).is_err());
+            let curve = EllipticCurve::new(3, 4, 53).unwrap();
+            assert_eq!(44, curve.order().unwrap());
+            assert_eq!(64, curve.quadratic_twist().unwrap().order().unwrap());
+            assert!(!curve.is_twist_secure(11).unwrap());
+            let curve = EllipticCurve::new(-1, 0, 71).unwrap();
+            assert!(!curve.is_twist_secure(3).unwrap());
+        }
     }
 }
@@ -0,0 +1,22 @@
+//! Elliptic curves over prime fields. Only `alloc` is needed: turning off the
+//! default `std` feature builds the library as `no_std`.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+pub mod curve;
+pub mod ext;
+pub mod ext_curve;
+pub mod group;
+pub mod montgomery;
+pub mod pairing;
+pub mod prime;
+pub mod report;
+/// The in-tree xorshift generator. It always backs `order_bsgs` and
+/// `order_fast`, but is public, along with `random_point` and
+/// `EllipticCurve::random`, only with the `rng` feature.
+#[cfg(feature = "rng")]
+pub mod rng;
+#[cfg(not(feature = "rng"))]
+pub(crate) mod rng;
+pub mod torsion;
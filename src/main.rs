@@ -1,11 +1,27 @@
-mod curve;
-mod group;
-mod prime;
-use curve::*;
-use group::*;
-use prime::Prime;
-
-fn main() -> Result<(), String> {
+use elliptic_curve::curve::*;
+use elliptic_curve::group::*;
+use elliptic_curve::prime::*;
+use std::process::ExitCode;
+
+pub const USAGE: &str = "usage: elliptic_curve <command> [args...]
+    demo
+    order  A B P
+    sum    A B P X0 Y0 X1 Y1
+    mul    A B P K X Y
+    points A B P";
+pub const NOT_AN_INTEGER: &str = "not an integer...";
+pub const WRONG_ARITY: &str = "wrong number of arguments...";
+
+fn parse(args: &[String], arity: usize) -> Result<Vec<Num>, String> {
+    if args.len() != arity {
+        return Err(format!("{}\n{}", WRONG_ARITY, USAGE));
+    }
+    args.iter()
+        .map(|arg| arg.parse().map_err(|_| NOT_AN_INTEGER.to_string()))
+        .collect()
+}
+
+fn demo() -> Result<(), String> {
     let prime = 47;
     let curve = EllipticCurve::new(-1, 0, prime)?;
     println!("Elliptic curve: {}", curve);
@@ -19,7 +35,7 @@ fn main() -> Result<(), String> {
         println!("{:>2}: {}", i, pt);
     }
 
-    for p in 2..100 {
+    for p in 5..100 {
         if p.is_prime()? {
             let curve = EllipticCurve::new(-1, 0, p).unwrap();
             println!(
@@ -33,3 +49,54 @@ fn main() -> Result<(), String> {
 
     Ok(())
 }
+
+fn run(args: &[String]) -> Result<(), String> {
+    let (cmd, rest) = match args.split_first() {
+        Some((cmd, rest)) => (cmd.as_str(), rest),
+        None => return Err(USAGE.to_string()),
+    };
+    match cmd {
+        "demo" => demo(),
+        "order" => {
+            let v = parse(rest, 3)?;
+            let curve = EllipticCurve::new(v[0], v[1], v[2])?;
+            println!("{}", curve.order()?);
+            Ok(())
+        }
+        "sum" => {
+            let v = parse(rest, 7)?;
+            let curve = EllipticCurve::new(v[0], v[1], v[2])?;
+            let pt0 = curve.affine(v[3], v[4])?;
+            let pt1 = curve.affine(v[5], v[6])?;
+            println!("{}", curve.sum(pt0, pt1)?);
+            Ok(())
+        }
+        "mul" => {
+            let v = parse(rest, 6)?;
+            let curve = EllipticCurve::new(v[0], v[1], v[2])?;
+            let pt = curve.affine(v[4], v[5])?;
+            println!("{}", curve.mul(v[3], pt)?);
+            Ok(())
+        }
+        "points" => {
+            let v = parse(rest, 3)?;
+            let curve = EllipticCurve::new(v[0], v[1], v[2])?;
+            for pt in curve.solutions()? {
+                println!("{}", pt);
+            }
+            Ok(())
+        }
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
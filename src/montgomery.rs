@@ -0,0 +1,332 @@
+use crate::curve::*;
+use crate::prime::*;
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// `coef_b * y^2 = x^3 + coef_a * x^2 + x (mod prime)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MontgomeryCurve {
+    coef_a: Num,
+    coef_b: Num,
+    prime: Num,
+}
+
+impl MontgomeryCurve {
+    pub fn new(coef_a: Num, coef_b: Num, prime: Num) -> Result<Self, String> {
+        if !prime.is_prime()? {
+            return Err(NOT_A_PRIME.to_string());
+        }
+        if prime <= 3 {
+            return Err(SMALL_CHARACTERISTIC.to_string());
+        }
+        let (a, b) = (coef_a.rem_euclid(prime), coef_b.rem_euclid(prime));
+        if b == 0 || mul_mod(a, a, prime) == 4 {
+            return Err(NOT_AN_NON_SINGULAR.to_string());
+        }
+        Ok(MontgomeryCurve {
+            coef_a: a,
+            coef_b: b,
+            prime,
+        })
+    }
+    pub fn coef_a(&self) -> Num {
+        self.coef_a
+    }
+    pub fn coef_b(&self) -> Num {
+        self.coef_b
+    }
+    pub fn prime(&self) -> Num {
+        self.prime
+    }
+    pub fn rhs(&self, x: Num) -> Result<Num, String> {
+        let (p, x) = (self.prime, x.rem_euclid(self.prime));
+        let ax2 = mul_mod(mul_mod(self.coef_a, x, p), x, p);
+        Ok(add_mod(add_mod(x.mod_pow(3, p)?, ax2, p), x, p))
+    }
+    pub fn is_on(&self, point: Point) -> bool {
+        match point {
+            Point::Inf => true,
+            Point::Affine(x, y) => match self.rhs(x) {
+                Ok(rhs) => {
+                    let p = self.prime;
+                    mul_mod(mul_mod(self.coef_b, y, p), y, p) == rhs
+                }
+                Err(_) => false,
+            },
+        }
+    }
+    pub fn order(&self) -> Result<Num, String> {
+        let b_inv = self.coef_b.mod_inverse(self.prime)?;
+        let values = (0..self.prime)
+            .map(|x| Ok(mul_mod(self.rhs(x)?, b_inv, self.prime)))
+            .collect::<Result<Vec<Num>, String>>()?;
+        Ok(legendre_batch(&values, self.prime)?
+            .iter()
+            .fold(1, |acc, l| acc + 1 + l))
+    }
+    /// Substitutes `x = coef_b * u - coef_a / 3`, `y = coef_b * v` to reach
+    /// `v^2 = u^3 + a * u + b`.
+    pub fn to_weierstrass(&self) -> Result<EllipticCurve, String> {
+        let (p, a, b) = (self.prime, self.coef_a, self.coef_b);
+        let num1 = add_mod(3, -mul_mod(a, a, p), p);
+        let coef1 = mul_mod(num1, mul_mod(3, mul_mod(b, b, p), p).mod_inverse(p)?, p);
+        let num0 = add_mod(mul_mod(2, a.mod_pow(3, p)?, p), -mul_mod(9, a, p), p);
+        let coef0 = mul_mod(num0, mul_mod(27, b.mod_pow(3, p)?, p).mod_inverse(p)?, p);
+        EllipticCurve::new(coef1, coef0, p)
+    }
+    pub fn to_weierstrass_point(&self, point: Point) -> Result<Point, String> {
+        if !self.is_on(point) {
+            return Err(NOT_ON_THE_CURVE.to_string());
+        }
+        let p = self.prime;
+        Ok(match point {
+            Point::Inf => Point::Inf,
+            Point::Affine(x, y) => Point::Affine(
+                mul_mod(
+                    add_mod(mul_mod(3, x, p), self.coef_a, p),
+                    mul_mod(3, self.coef_b, p).mod_inverse(p)?,
+                    p,
+                ),
+                mul_mod(y, self.coef_b.mod_inverse(p)?, p),
+            ),
+        })
+    }
+    /// A short Weierstrass curve has a Montgomery model exactly when it has a
+    /// root `alpha` of the cubic with `3 * alpha^2 + a` a square; otherwise
+    /// `None`.
+    pub fn from_weierstrass(curve: &EllipticCurve) -> Result<Option<MontgomeryCurve>, String> {
+        let p = curve.prime();
+        for alpha in 0..p {
+            if curve.rhs(alpha)? != 0 {
+                continue;
+            }
+            let t = add_mod(mul_mod(3, mul_mod(alpha, alpha, p), p), curve.coef1(), p);
+            if t == 0 {
+                continue;
+            }
+            if let Some(r) = t.sqrt_mod_prime(p)? {
+                let s = r.mod_inverse(p)?;
+                return Ok(Some(MontgomeryCurve::new(
+                    mul_mod(mul_mod(3, alpha, p), s, p),
+                    s,
+                    p,
+                )?));
+            }
+        }
+        Ok(None)
+    }
+    /// x-only Montgomery ladder. The point at infinity has no x-coordinate;
+    /// as in X25519 it comes out as `X * Z^(p - 2) = 0`.
+    pub fn ladder(&self, scalar: Num, x: Num) -> Result<Num, String> {
+        if scalar < 0 {
+            return Err(NOT_AN_NON_NEG.to_string());
+        }
+        let p = self.prime;
+        let x = x.rem_euclid(p);
+        let a24 = mul_mod(add_mod(self.coef_a, 2, p), 4.mod_inverse(p)?, p);
+        let mut r0 = (1, 0);
+        let mut r1 = (x, 1);
+        for i in (0..Num::BITS - scalar.leading_zeros()).rev() {
+            let sum = self.xadd(r0, r1, x);
+            if (scalar >> i) & 1 == 1 {
+                r0 = sum;
+                r1 = self.xdbl(r1, a24);
+            } else {
+                r1 = sum;
+                r0 = self.xdbl(r0, a24);
+            }
+        }
+        Ok(mul_mod(r0.0, r0.1.mod_pow(p - 2, p)?, p))
+    }
+}
+impl MontgomeryCurve {
+    fn xdbl(&self, (x, z): (Num, Num), a24: Num) -> (Num, Num) {
+        let p = self.prime;
+        let (s, d) = (add_mod(x, z, p), add_mod(x, -z, p));
+        let (plus, minus) = (mul_mod(s, s, p), mul_mod(d, d, p));
+        let t = add_mod(plus, -minus, p);
+        (
+            mul_mod(plus, minus, p),
+            mul_mod(t, add_mod(minus, mul_mod(a24, t, p), p), p),
+        )
+    }
+    fn xadd(&self, (x2, z2): (Num, Num), (x3, z3): (Num, Num), diff: Num) -> (Num, Num) {
+        let p = self.prime;
+        let u = mul_mod(add_mod(x2, -z2, p), add_mod(x3, z3, p), p);
+        let v = mul_mod(add_mod(x2, z2, p), add_mod(x3, -z3, p), p);
+        let (sum, dif) = (add_mod(u, v, p), add_mod(u, -v, p));
+        (mul_mod(sum, sum, p), mul_mod(diff, mul_mod(dif, dif, p), p))
+    }
+}
+impl core::fmt::Display for MontgomeryCurve {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "{} * y^2 = x^3 + {} * x^2 + x (mod {})",
+            self.coef_b, self.coef_a, self.prime
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::group::*;
+
+    mod test_montgomery {
+        use super::*;
+
+        fn brute_force(curve: &MontgomeryCurve) -> Vec<Point> {
+            let mut points = vec![Point::Inf];
+            for x in 0..curve.prime() {
+                for y in 0..curve.prime() {
+                    if curve.is_on(Point::Affine(x, y)) {
+                        points.push(Point::Affine(x, y));
+                    }
+                }
+            }
+            points
+        }
+
+        #[test]
+        fn test_new() {
+            assert!(MontgomeryCurve::new(3, 1, 9).is_err());
+            assert!(MontgomeryCurve::new(3, 1, 3).is_err());
+            assert!(MontgomeryCurve::new(3, 0, 7).is_err());
+            assert!(MontgomeryCurve::new(2, 1, 7).is_err());
+            assert!(MontgomeryCurve::new(-2, 1, 7).is_err());
+            let curve = MontgomeryCurve::new(-4, 8, 7).unwrap();
+            assert_eq!((3, 1), (curve.coef_a(), curve.coef_b()));
+            assert_eq!("1 * y^2 = x^3 + 3 * x^2 + x (mod 7)", curve.to_string());
+        }
+        #[test]
+        fn test_to_weierstrass() {
+            for p in [5, 7, 11, 13, 101] {
+                for a in 0..p {
+                    for b in 1..p {
+                        let Ok(curve) = MontgomeryCurve::new(a, b, p) else {
+                            continue;
+                        };
+                        let order = curve.order().unwrap();
+                        let points = if p < 20 { brute_force(&curve) } else { vec![] };
+                        if p < 20 {
+                            assert_eq!(points.len() as Num, order);
+                        }
+                        assert_eq!(0, order % 4);
+                        let weier = curve.to_weierstrass().unwrap();
+                        assert_eq!(order, weier.order().unwrap());
+                        for pt in points {
+                            assert!(weier.is_on(curve.to_weierstrass_point(pt).unwrap()));
+                        }
+                    }
+                }
+            }
+            let curve = MontgomeryCurve::new(3, 1, 7).unwrap();
+            assert!(curve.to_weierstrass_point(Point::Affine(1, 1)).is_err());
+        }
+        #[test]
+        fn test_large_prime() {
+            let p = 1_000_000_007;
+            assert!(MontgomeryCurve::new(p - 2, 1, p).is_err());
+            for (a, b) in [(486662, 1), (p - 3, p - 5)] {
+                let curve = MontgomeryCurve::new(a, b, p).unwrap();
+                let weier = curve.to_weierstrass().unwrap();
+                let b_inv = b.mod_inverse(p).unwrap();
+                let mut tested = 0;
+                for x in (p - 100..p).chain(1..100) {
+                    let rhs = mul_mod(curve.rhs(x).unwrap(), b_inv, p);
+                    let Some(y) = rhs.sqrt_mod_prime(p).unwrap() else {
+                        continue;
+                    };
+                    assert!(curve.is_on(Point::Affine(x, y)));
+                    assert!(!curve.is_on(Point::Affine(x, y + 1)));
+                    let pt = curve.to_weierstrass_point(Point::Affine(x, y)).unwrap();
+                    assert!(weier.is_on(pt));
+                    tested += 1;
+                }
+                assert!(tested > 0);
+            }
+            let p = 50021;
+            let curve = MontgomeryCurve::new(p - 3, p - 5, p).unwrap();
+            let weier = curve.to_weierstrass().unwrap();
+            assert_eq!(curve.order().unwrap(), weier.order().unwrap());
+            let round = MontgomeryCurve::from_weierstrass(&weier).unwrap().unwrap();
+            assert_eq!(weier, round.to_weierstrass().unwrap());
+        }
+        #[test]
+        fn test_ladder() {
+            for (a, b, p) in [
+                (3, 1, 7),
+                (5, 3, 101),
+                (6, 1, 1009),
+                (486662, 1, 10007),
+                (486662, 1, 1_000_000_007),
+                (-3, -5, 2147390939),
+            ] {
+                let curve = MontgomeryCurve::new(a, b, p).unwrap();
+                let weier = curve.to_weierstrass().unwrap();
+                let (a, b) = (curve.coef_a(), curve.coef_b());
+                let to_x = |pt: Point| match pt {
+                    Point::Inf => 0,
+                    Point::Affine(u, _) => add_mod(
+                        mul_mod(b, u, p),
+                        -mul_mod(a, 3.mod_inverse(p).unwrap(), p),
+                        p,
+                    ),
+                };
+                let mut tested = 0;
+                for x in (0..p).step_by(1 + p as usize / 50) {
+                    let rhs = mul_mod(curve.rhs(x).unwrap(), b.mod_inverse(p).unwrap(), p);
+                    let Some(y) = rhs.sqrt_mod_prime(p).unwrap() else {
+                        continue;
+                    };
+                    let pt = curve.to_weierstrass_point(Point::Affine(x, y)).unwrap();
+                    assert_eq!(x, to_x(pt));
+                    for k in [0, 1, 2, 3, 4, 7, 12, 255, 1000, p - 1, p + 1, Num::MAX] {
+                        assert_eq!(to_x(weier.mul(k, pt).unwrap()), curve.ladder(k, x).unwrap());
+                    }
+                    tested += 1;
+                }
+                assert!(tested > 0);
+            }
+            let curve = MontgomeryCurve::new(3, 1, 7).unwrap();
+            assert!(curve.ladder(-1, 0).is_err());
+        }
+        #[test]
+        fn test_from_weierstrass() {
+            for p in [5, 7, 11, 13, 101] {
+                for a in 0..p {
+                    for b in 0..p {
+                        if (4 * a * a * a + 27 * b * b) % p == 0 {
+                            continue;
+                        }
+                        let weier = EllipticCurve::new(a, b, p).unwrap();
+                        match MontgomeryCurve::from_weierstrass(&weier).unwrap() {
+                            Some(curve) => {
+                                assert_eq!(weier.order().unwrap(), curve.order().unwrap());
+                                assert_eq!(weier, curve.to_weierstrass().unwrap());
+                            }
+                            None => assert!(
+                                weier.order().unwrap() % 4 != 0
+                                    || weier.solutions().unwrap().iter().any(|pt| {
+                                        pt.y() == Some(0)
+                                            && !(3 * pt.x().unwrap().pow(2) + a)
+                                                .rem_euclid(p)
+                                                .is_quadratic_residue(p)
+                                                .unwrap()
+                                    })
+                            ),
+                        }
+                    }
+                }
+            }
+            let curve = MontgomeryCurve::new(5, 3, 101).unwrap();
+            let round = MontgomeryCurve::from_weierstrass(&curve.to_weierstrass().unwrap())
+                .unwrap()
+                .unwrap();
+            assert_eq!(curve.order().unwrap(), round.order().unwrap());
+        }
+    }
+}
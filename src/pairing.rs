@@ -0,0 +1,195 @@
+use crate::curve::*;
+use crate::ext::*;
+use crate::prime::*;
+use alloc::string::{String, ToString};
+
+pub const NOT_A_TORSION: &str = "not an n-torsion point...";
+pub const DEGENERATE: &str = "a degenerate pairing...";
+
+pub trait Pairing {
+    fn weil_pairing(&self, p: Point, q: Point, n: Num) -> Result<Gf2, String>;
+}
+
+impl EllipticCurve {
+    fn embed(&self, point: Point) -> Result<ExtPoint, String> {
+        Ok(match self.represent(point)? {
+            Point::Inf => ExtPoint::Inf,
+            Point::Affine(x, y) => {
+                ExtPoint::Affine(Gf2::new(x, 0, self.prime())?, Gf2::new(y, 0, self.prime())?)
+            }
+        })
+    }
+    fn has_distortion(&self) -> bool {
+        self.coef0() == 0 && self.prime().rem_euclid(4) == 3
+    }
+    fn distort(&self, point: Point) -> Result<ExtPoint, String> {
+        let p = self.prime();
+        Ok(match self.represent(point)? {
+            Point::Inf => ExtPoint::Inf,
+            Point::Affine(x, y) => {
+                let nr = Gf2::non_residue(p)?;
+                let s = match (-nr.mod_inverse(p)?).sqrt_mod_prime(p)? {
+                    Some(s) => s,
+                    None => return Err(DEGENERATE.to_string()),
+                };
+                ExtPoint::Affine(Gf2::new(-x, 0, p)?, Gf2::new(0, mul_mod(s, y, p), p)?)
+            }
+        })
+    }
+    fn line(&self, pt0: ExtPoint, pt1: ExtPoint, at: ExtPoint) -> Result<(Gf2, ExtPoint), String> {
+        let one = Gf2::new(1, 0, self.prime())?;
+        let (x0, y0, x1, y1) = match (pt0, pt1) {
+            (ExtPoint::Inf, _) => return Ok((one, pt1)),
+            (_, ExtPoint::Inf) => return Ok((one, pt0)),
+            (ExtPoint::Affine(x0, y0), ExtPoint::Affine(x1, y1)) => (x0, y0, x1, y1),
+        };
+        let (xs, ys) = match at {
+            ExtPoint::Inf => return Err(DEGENERATE.to_string()),
+            ExtPoint::Affine(xs, ys) => (xs, ys),
+        };
+        if x0 == x1 && y0.add(&y1)?.is_zero() {
+            return Ok((xs.sub(&x0)?, ExtPoint::Inf));
+        }
+        let slope = if x0 == x1 {
+            let three = Gf2::new(3, 0, self.prime())?;
+            let coef1 = Gf2::new(self.coef1(), 0, self.prime())?;
+            three
+                .mul(&x0)?
+                .mul(&x0)?
+                .add(&coef1)?
+                .mul(&y0.add(&y0)?.inverse()?)?
+        } else {
+            y1.sub(&y0)?.mul(&x1.sub(&x0)?.inverse()?)?
+        };
+        let x2 = slope.mul(&slope)?.sub(&x0)?.sub(&x1)?;
+        let y2 = slope.mul(&x0.sub(&x2)?)?.sub(&y0)?;
+        let num = ys.sub(&y0)?.sub(&slope.mul(&xs.sub(&x0)?)?)?;
+        let den = xs.sub(&x2)?;
+        if den.is_zero() {
+            return Err(DEGENERATE.to_string());
+        }
+        Ok((num.mul(&den.inverse()?)?, ExtPoint::Affine(x2, y2)))
+    }
+    fn miller(&self, base: ExtPoint, n: Num, at: ExtPoint) -> Result<Gf2, String> {
+        let mut f = Gf2::new(1, 0, self.prime())?;
+        let mut t = base;
+        for i in (0..Num::BITS - 1 - n.leading_zeros()).rev() {
+            let (g, dbl) = self.line(t, t, at)?;
+            f = f.mul(&f)?.mul(&g)?;
+            t = dbl;
+            if (n >> i) & 1 == 1 {
+                let (g, sum) = self.line(t, base, at)?;
+                f = f.mul(&g)?;
+                t = sum;
+            }
+        }
+        Ok(f)
+    }
+}
+impl Pairing for EllipticCurve {
+    fn weil_pairing(&self, p: Point, q: Point, n: Num) -> Result<Gf2, String> {
+        if n < 1 {
+            return Err(NOT_A_POS.to_string());
+        }
+        if !self.mul(n, p)?.is_inf() || !self.mul(n, q)?.is_inf() {
+            return Err(NOT_A_TORSION.to_string());
+        }
+        let one = Gf2::new(1, 0, self.prime())?;
+        let pe = self.embed(p)?;
+        let qe = match self.has_distortion() {
+            true => self.distort(q)?,
+            false => self.embed(q)?,
+        };
+        if pe == ExtPoint::Inf || qe == ExtPoint::Inf || pe == qe {
+            return Ok(one);
+        }
+        let num = self.miller(pe, n, qe)?;
+        let den = self.miller(qe, n, pe)?;
+        if num.is_zero() || den.is_zero() {
+            return Err(DEGENERATE.to_string());
+        }
+        let e = num.mul(&den.inverse()?)?;
+        Ok(match n.rem_euclid(2) {
+            1 => e.neg(),
+            _ => e,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::group::*;
+
+    mod test_pairing {
+        use super::*;
+
+        #[test]
+        fn test_weil_pairing() {
+            let curve = EllipticCurve::new(-1, 0, 71).unwrap();
+            let one = Gf2::new(1, 0, 71).unwrap();
+            for n in [3, 9] {
+                let pt = curve
+                    .solutions()
+                    .unwrap()
+                    .into_iter()
+                    .find(|&pt| curve.cyclic_group(pt).unwrap().len() as Num == n)
+                    .unwrap();
+                let e = curve.weil_pairing(pt, pt, n).unwrap();
+                assert_ne!(one, e);
+                assert_eq!(one, e.pow(n).unwrap());
+                for a in 1..n {
+                    for b in 1..n {
+                        let pa = curve.mul(a, pt).unwrap();
+                        let pb = curve.mul(b, pt).unwrap();
+                        assert_eq!(
+                            e.pow(a * b).unwrap(),
+                            curve.weil_pairing(pa, pb, n).unwrap()
+                        );
+                    }
+                }
+            }
+        }
+        #[test]
+        fn test_weil_pairing_large_prime() {
+            // 50051 = 3 (mod 4) and 3 divides #E = 50052.
+            let p = 50051;
+            let curve = EllipticCurve::new(-1, 0, p).unwrap();
+            let one = Gf2::new(1, 0, p).unwrap();
+            let pt = (1..p)
+                .flat_map(|x| curve.points_with_x(x).unwrap())
+                .map(|pt| curve.mul((p + 1) / 3, pt).unwrap())
+                .find(|pt| !pt.is_inf())
+                .unwrap();
+            let e = curve.weil_pairing(pt, pt, 3).unwrap();
+            assert_ne!(one, e);
+            assert_eq!(one, e.pow(3).unwrap());
+            for a in 1..3 {
+                for b in 1..3 {
+                    let pa = curve.mul(a, pt).unwrap();
+                    let pb = curve.mul(b, pt).unwrap();
+                    assert_eq!(
+                        e.pow(a * b).unwrap(),
+                        curve.weil_pairing(pa, pb, 3).unwrap()
+                    );
+                }
+            }
+        }
+        #[test]
+        fn test_weil_pairing_invalid() {
+            let curve = EllipticCurve::new(-1, 0, 71).unwrap();
+            let pt = Point::Affine(0, 0);
+            assert!(curve.weil_pairing(pt, pt, 0).is_err());
+            assert!(curve.weil_pairing(pt, Point::Affine(0, 1), 2).is_err());
+            let gene = curve
+                .solutions()
+                .unwrap()
+                .into_iter()
+                .find(|&pt| curve.cyclic_group(pt).unwrap().len() == 36)
+                .unwrap();
+            assert!(curve.weil_pairing(gene, gene, 3).is_err());
+            let one = Gf2::new(1, 0, 71).unwrap();
+            assert_eq!(one, curve.weil_pairing(Point::Inf, gene, 36).unwrap());
+        }
+    }
+}
@@ -1,21 +1,112 @@
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
 pub const NOT_AN_NON_NEG: &str = "not an non-negative integer...";
 pub const NOT_A_POS: &str = "not a positive integer...";
 pub const NOT_A_PRIME: &str = "not a prime number...";
+pub const NOT_AN_ODD_POS: &str = "not an odd positive integer...";
+pub const NOT_COPRIME: &str = "not coprime integers...";
+pub const INVALID_TABLE: &str = "not a valid power table...";
 
 pub type Num = i32;
 
 pub trait Prime<T> {
     fn is_prime(&self) -> Result<bool, &str>;
     fn prime_factors(&self) -> Result<Vec<(T, T)>, &str>;
-    #[allow(dead_code)]
     fn is_prime_pow(&self) -> Result<bool, &str>;
     fn inner_gcd(&self, max: Self) -> Self;
     fn gcd(&self, other: Self) -> Self;
+    /// `floor(√self)`, or `NOT_AN_NON_NEG` below zero. Not called `isqrt`:
+    /// the inherent `i32::isqrt` (Rust 1.84) would win every `n.isqrt()` call
+    /// and panic on negatives instead of returning this error.
+    fn floor_sqrt(&self) -> Result<T, String>;
+    fn euler_phi(&self) -> Result<T, String>;
+    fn prime_factors_flat(&self) -> Result<Vec<T>, String>;
+    fn divisors(&self) -> Result<Vec<T>, String>;
 }
 pub trait Field<T> {
     fn mod_pow(&self, exp: Self, modulo: Self) -> Result<T, &str>;
+    fn mod_inverse(&self, modulo: Self) -> Result<T, String>;
+    fn qr_sieve(&self) -> Result<Vec<bool>, String>;
     fn qr_mod_prime(&self) -> Result<Vec<T>, String>;
+    /// Euler's criterion, `O(log prime)` with no sieve: a bare `Num` has
+    /// nowhere to keep one, so curves scanning every `x` use their own
+    /// cached `residue_set` instead.
     fn legendre(&self, prime: Self) -> Result<T, String>;
+    fn jacobi(&self, n: Self) -> Result<T, String>;
+    fn mult_order(&self, prime: Self) -> Result<T, String>;
+    fn primitive_root(&self) -> Result<T, String>;
+    fn sqrt_mod_prime(&self, prime: Self) -> Result<Option<T>, String>;
+    fn sqrts_mod_prime(&self, prime: Self) -> Result<Vec<T>, String>;
+    fn is_quadratic_residue(&self, prime: Self) -> Result<bool, String>;
+    fn mod_pow_windowed(&self, exp: Self, modulo: Self, table: &[T]) -> Result<T, String>;
+}
+
+pub fn legendre_batch(values: &[Num], prime: Num) -> Result<Vec<Num>, String> {
+    let sieve = prime.qr_sieve()?;
+    Ok(values
+        .iter()
+        .map(|v| match v.rem_euclid(prime) {
+            0 => 0,
+            r if sieve[r as usize] => 1,
+            _ => -1,
+        })
+        .collect())
+}
+
+/// `base^0, base^1, ..., base^(2^window - 1)` reduced mod `modulo`, for use
+/// with `mod_pow_windowed`. Empty when `modulo < 1` or `window` is not in `1..=16`.
+pub fn pow_table(base: Num, modulo: Num, window: u32) -> Vec<Num> {
+    if modulo < 1 || !(1..=16).contains(&window) {
+        return vec![];
+    }
+    let base = base.rem_euclid(modulo) as i64;
+    let mut table = vec![1 % modulo];
+    for i in 1..1 << window {
+        table.push((table[i - 1] as i64 * base % modulo as i64) as Num);
+    }
+    table
+}
+
+/// `a + b mod modulo` in `0..modulo`, summed in `i64` so it cannot overflow
+/// for any `Num` inputs.
+pub fn add_mod(a: Num, b: Num, modulo: Num) -> Num {
+    (a as i64 + b as i64).rem_euclid(modulo as i64) as Num
+}
+
+/// `a * b mod modulo` in `0..modulo`, multiplied in `i64` so it cannot
+/// overflow for any `Num` inputs.
+pub fn mul_mod(a: Num, b: Num, modulo: Num) -> Num {
+    (a as i64 * b as i64).rem_euclid(modulo as i64) as Num
+}
+
+pub fn batch_inverse(values: &[Num], modulo: Num) -> Result<Vec<Num>, String> {
+    if modulo < 1 {
+        return Err(NOT_A_POS.to_string());
+    }
+    if values.is_empty() {
+        return Ok(vec![]);
+    }
+    let mut prefix = Vec::with_capacity(values.len());
+    let mut acc = 1;
+    for v in values {
+        if v.rem_euclid(modulo) == 0 {
+            return Err(NOT_COPRIME.to_string());
+        }
+        acc = mul_mod(acc, *v, modulo);
+        prefix.push(acc);
+    }
+    let mut inv = acc.mod_inverse(modulo)?;
+    let mut res = vec![0; values.len()];
+    for i in (1..values.len()).rev() {
+        res[i] = mul_mod(inv, prefix[i - 1], modulo);
+        inv = mul_mod(inv, values[i], modulo);
+    }
+    res[0] = inv;
+    Ok(res)
 }
 
 impl Prime<Num> for Num {
@@ -33,7 +124,7 @@ impl Prime<Num> for Num {
             return Ok(false);
         }
         let mut odd = 3;
-        while odd * odd <= *self {
+        while odd <= *self / odd {
             if self.rem_euclid(odd) == 0 {
                 return Ok(false);
             }
@@ -51,7 +142,7 @@ impl Prime<Num> for Num {
         let mut res = Vec::new();
         let mut n = *self;
         let mut p = 2;
-        while p * p <= *self {
+        while p <= n / p {
             let mut e = 0;
             if n.rem_euclid(p) == 0 {
                 while n.rem_euclid(p) == 0 {
@@ -77,7 +168,55 @@ impl Prime<Num> for Num {
         max.rem_euclid(*self).inner_gcd(*self)
     }
     fn gcd(&self, other: Self) -> Self {
-        std::cmp::min(self.abs(), other.abs()).inner_gcd(std::cmp::max(self.abs(), other.abs()))
+        core::cmp::min(self.abs(), other.abs()).inner_gcd(core::cmp::max(self.abs(), other.abs()))
+    }
+    fn floor_sqrt(&self) -> Result<Self, String> {
+        if *self < 0 {
+            return Err(NOT_AN_NON_NEG.to_string());
+        }
+        if *self < 2 {
+            return Ok(*self);
+        }
+        let mut x = *self;
+        let mut y = x / 2 + x % 2;
+        while y < x {
+            x = y;
+            y = (x + self / x) / 2;
+        }
+        Ok(x)
+    }
+    fn euler_phi(&self) -> Result<Self, String> {
+        Ok(self
+            .prime_factors()?
+            .iter()
+            .map(|&(p, e)| p.pow(e as u32 - 1) * (p - 1))
+            .product())
+    }
+    fn prime_factors_flat(&self) -> Result<Vec<Self>, String> {
+        Ok(self
+            .prime_factors()?
+            .iter()
+            .flat_map(|&(p, e)| core::iter::repeat_n(p, e as usize))
+            .collect())
+    }
+    fn divisors(&self) -> Result<Vec<Self>, String> {
+        let mut divs = vec![1];
+        for (p, e) in self.prime_factors()? {
+            let mut next = Vec::with_capacity(divs.len() * (e as usize + 1));
+            for d in &divs {
+                let mut pk = 1;
+                for k in 0..=e {
+                    next.push(d * pk);
+                    // Stop at `p^e`: one more power can pass `Num::MAX`.
+                    if k < e {
+                        pk *= p;
+                    }
+                }
+            }
+            divs = next;
+        }
+        divs.sort();
+        Ok(divs)
     }
 }
 impl Field<Num> for Num {
@@ -88,42 +227,216 @@ impl Field<Num> for Num {
         if exp < 0 {
             return Err(NOT_AN_NON_NEG);
         }
-        Ok(match (self, exp, modulo) {
+        let res = match (self.rem_euclid(modulo), exp, modulo) {
             (_, _, 1) => 0,
             (_, 0, _) => 1,
             (0, _, _) => 0,
-            _ => {
-                let mut res = self.rem_euclid(modulo);
-                for _ in 1..exp {
-                    res *= self;
-                    res = res.rem_euclid(modulo);
+            (base, _, _) => {
+                let (mut base, mut exp, modulo) = (base as i64, exp, modulo as i64);
+                let mut res = 1;
+                while exp > 0 {
+                    if exp & 1 == 1 {
+                        res = res * base % modulo;
+                    }
+                    base = base * base % modulo;
+                    exp >>= 1;
                 }
-                res
+                res as Num
             }
-        })
+        };
+        debug_assert!((0..modulo).contains(&res));
+        Ok(res)
     }
-    fn qr_mod_prime(&self) -> Result<Vec<Self>, String> {
+    fn mod_inverse(&self, modulo: Self) -> Result<Self, String> {
+        if modulo < 1 {
+            return Err(NOT_A_POS.to_string());
+        }
+        let (mut r0, mut r1) = (modulo, self.rem_euclid(modulo));
+        let (mut t0, mut t1) = (0, 1);
+        while r1 != 0 {
+            let q = r0 / r1;
+            (r0, r1) = (r1, r0 - q * r1);
+            (t0, t1) = (t1, t0 - q * t1);
+        }
+        if r0 != 1 {
+            return Err(NOT_COPRIME.to_string());
+        }
+        Ok(t0.rem_euclid(modulo))
+    }
+    fn qr_sieve(&self) -> Result<Vec<bool>, String> {
         if !self.is_prime()? {
             return Err(NOT_A_PRIME.to_string());
         }
-        let mut qrs = vec![0, 1];
-        for i in 2..*self {
-            if i.mod_pow((self - 1) / 2, *self)? == 1 {
-                qrs.push(i);
-            }
+        let mut sieve = vec![false; *self as usize];
+        for i in 0..=*self / 2 {
+            sieve[mul_mod(i, i, *self) as usize] = true;
         }
-        Ok(qrs)
+        Ok(sieve)
+    }
+    fn qr_mod_prime(&self) -> Result<Vec<Self>, String> {
+        Ok(self
+            .qr_sieve()?
+            .iter()
+            .enumerate()
+            .filter(|(_, &is_qr)| is_qr)
+            .map(|(i, _)| i as Num)
+            .collect())
     }
     fn legendre(&self, prime: Self) -> Result<Self, String> {
-        let qrs = prime.qr_mod_prime()?;
-        if self.gcd(prime) != 1 {
+        if !prime.is_prime()? {
+            return Err(NOT_A_PRIME.to_string());
+        }
+        if self.rem_euclid(prime) == 0 {
             return Ok(0);
         }
-        Ok(match qrs.contains(self) {
-            true => 1,
-            false => -1,
+        Ok(match self.mod_pow((prime - 1) / 2, prime)? {
+            1 => 1,
+            _ => -1,
+        })
+    }
+    fn jacobi(&self, n: Self) -> Result<Self, String> {
+        if n <= 0 || n.rem_euclid(2) == 0 {
+            return Err(NOT_AN_ODD_POS.to_string());
+        }
+        let mut a = self.rem_euclid(n);
+        let mut n = n;
+        let mut res = 1;
+        while a != 0 {
+            while a.rem_euclid(2) == 0 {
+                a /= 2;
+                if matches!(n.rem_euclid(8), 3 | 5) {
+                    res = -res;
+                }
+            }
+            core::mem::swap(&mut a, &mut n);
+            if a.rem_euclid(4) == 3 && n.rem_euclid(4) == 3 {
+                res = -res;
+            }
+            a = a.rem_euclid(n);
+        }
+        Ok(match n {
+            1 => res,
+            _ => 0,
         })
     }
+    fn mult_order(&self, prime: Self) -> Result<Self, String> {
+        if !prime.is_prime()? {
+            return Err(NOT_A_PRIME.to_string());
+        }
+        if self.gcd(prime) != 1 {
+            return Err(NOT_COPRIME.to_string());
+        }
+        let mut ord = prime - 1;
+        for (p, _) in ord.prime_factors()? {
+            while ord.rem_euclid(p) == 0 && self.mod_pow(ord / p, prime)? == 1 {
+                ord /= p;
+            }
+        }
+        Ok(ord)
+    }
+    fn primitive_root(&self) -> Result<Self, String> {
+        if !self.is_prime()? {
+            return Err(NOT_A_PRIME.to_string());
+        }
+        let factors = (self - 1).prime_factors()?;
+        for g in 1..*self {
+            let mut is_root = true;
+            for (p, _) in &factors {
+                if g.mod_pow((self - 1) / p, *self)? == 1 {
+                    is_root = false;
+                    break;
+                }
+            }
+            if is_root {
+                return Ok(g);
+            }
+        }
+        // `GF(p)*` is cyclic, so some `g` in `1..p` generates it for every prime.
+        unreachable!("every prime has a primitive root")
+    }
+    fn sqrt_mod_prime(&self, prime: Self) -> Result<Option<Self>, String> {
+        if !prime.is_prime()? {
+            return Err(NOT_A_PRIME.to_string());
+        }
+        let a = self.rem_euclid(prime);
+        if a == 0 || prime == 2 {
+            return Ok(Some(a));
+        }
+        if a.mod_pow((prime - 1) / 2, prime)? != 1 {
+            return Ok(None);
+        }
+        let mut q = prime - 1;
+        let mut s = 0;
+        while q.rem_euclid(2) == 0 {
+            q /= 2;
+            s += 1;
+        }
+        let mut z = 2;
+        while z.mod_pow((prime - 1) / 2, prime)? != prime - 1 {
+            z += 1;
+        }
+        let mut m = s;
+        let mut c = z.mod_pow(q, prime)?;
+        let mut t = a.mod_pow(q, prime)?;
+        let mut r = a.mod_pow((q + 1) / 2, prime)?;
+        while t != 1 {
+            let mut i = 0;
+            let mut t2 = t;
+            while t2 != 1 {
+                t2 = mul_mod(t2, t2, prime);
+                i += 1;
+            }
+            let b = c.mod_pow(1 << (m - i - 1), prime)?;
+            m = i;
+            c = mul_mod(b, b, prime);
+            t = mul_mod(t, c, prime);
+            r = mul_mod(r, b, prime);
+        }
+        Ok(Some(r))
+    }
+    fn sqrts_mod_prime(&self, prime: Self) -> Result<Vec<Self>, String> {
+        Ok(match self.sqrt_mod_prime(prime)? {
+            None => vec![],
+            Some(r) if r == (prime - r).rem_euclid(prime) => vec![r],
+            Some(r) => {
+                let mut roots = vec![r, prime - r];
+                roots.sort();
+                roots
+            }
+        })
+    }
+    fn is_quadratic_residue(&self, prime: Self) -> Result<bool, String> {
+        Ok(self.legendre(prime)? == 1)
+    }
+    fn mod_pow_windowed(&self, exp: Self, modulo: Self, table: &[Self]) -> Result<Self, String> {
+        if modulo < 1 {
+            return Err(NOT_A_POS.to_string());
+        }
+        if exp < 0 {
+            return Err(NOT_AN_NON_NEG.to_string());
+        }
+        let window = table.len().trailing_zeros();
+        if table.len() < 2
+            || !table.len().is_power_of_two()
+            || table[0] != 1 % modulo
+            || table[1] != self.rem_euclid(modulo)
+            || table[table.len() - 1] != self.mod_pow(table.len() as Num - 1, modulo)?
+        {
+            return Err(INVALID_TABLE.to_string());
+        }
+        let modulo = modulo as i64;
+        let mask = (1 << window) - 1;
+        let mut res = 1 % modulo;
+        let mut shift = (Num::BITS - exp.leading_zeros()).div_ceil(window) * window;
+        while shift > 0 {
+            shift -= window;
+            for _ in 0..window {
+                res = res * res % modulo;
+            }
+            res = res * table[((exp >> shift) & mask) as usize] as i64 % modulo;
+        }
+        Ok(res as Num)
+    }
 }
 
 #[cfg(test)]
@@ -140,10 +453,24 @@ mod test {
             assert!(!1.is_prime().unwrap());
             assert!(2.is_prime().unwrap());
             assert!(97.is_prime().unwrap());
+            assert!(Num::MAX.is_prime().unwrap());
+            assert!(!(46349 * 46327).is_prime().unwrap());
         }
         #[test]
         fn test_prime_factors() {
             assert_eq!(vec![(2, 2), (5, 1), (23, 1)], 460.prime_factors().unwrap());
+            assert_eq!(vec![(2, 2), (1009, 1)], 4036.prime_factors().unwrap());
+            assert_eq!(vec![(2, 10)], 1024.prime_factors().unwrap());
+            assert_eq!(vec![(3, 2), (7, 2)], 441.prime_factors().unwrap());
+            assert_eq!(
+                vec![(1009, 1), (1013, 1)],
+                (1009 * 1013).prime_factors().unwrap()
+            );
+            assert_eq!(
+                vec![(46327, 1), (46349, 1)],
+                (46349 * 46327).prime_factors().unwrap()
+            );
+            assert_eq!(Vec::<(Num, Num)>::new(), 1.prime_factors().unwrap());
             assert!(0.prime_factors().is_err());
             assert!((-3).prime_factors().is_err());
         }
@@ -181,9 +508,74 @@ mod test {
             assert_eq!(1, 2.gcd(3));
             assert_eq!(2, 2.gcd(4));
         }
+        #[test]
+        fn test_floor_sqrt() {
+            assert!((-1).floor_sqrt().is_err());
+            assert_eq!(0, 0.floor_sqrt().unwrap());
+            assert_eq!(1, 1.floor_sqrt().unwrap());
+            assert_eq!(1, 3.floor_sqrt().unwrap());
+            assert_eq!(2, 4.floor_sqrt().unwrap());
+            assert_eq!(3, 15.floor_sqrt().unwrap());
+            assert_eq!(4, 16.floor_sqrt().unwrap());
+            assert_eq!(9, 99.floor_sqrt().unwrap());
+            assert_eq!(46340, Num::MAX.floor_sqrt().unwrap());
+            for n in 0..10000 {
+                let r = n.floor_sqrt().unwrap();
+                assert!(r * r <= n && n < (r + 1) * (r + 1));
+                assert_eq!(n.isqrt(), r);
+            }
+        }
+        #[test]
+        fn test_prime_factors_flat() {
+            assert!(0.prime_factors_flat().is_err());
+            assert!((-4).prime_factors_flat().is_err());
+            assert_eq!(Vec::<Num>::new(), 1.prime_factors_flat().unwrap());
+            assert_eq!(vec![2, 2, 5, 23], 460.prime_factors_flat().unwrap());
+            assert_eq!(vec![71], 71.prime_factors_flat().unwrap());
+            assert_eq!(vec![2, 2, 2, 3, 3], 72.prime_factors_flat().unwrap());
+            for n in 1..500 {
+                assert_eq!(n, n.prime_factors_flat().unwrap().iter().product::<Num>());
+            }
+        }
+        #[test]
+        fn test_divisors() {
+            assert!(0.divisors().is_err());
+            assert!((-12).divisors().is_err());
+            assert_eq!(vec![1], 1.divisors().unwrap());
+            assert_eq!(vec![1, 2, 3, 4, 6, 12], 12.divisors().unwrap());
+            assert_eq!(vec![1, 71], 71.divisors().unwrap());
+            for n in 1..500 {
+                let expected: Vec<Num> = (1..=n).filter(|d| n % d == 0).collect();
+                assert_eq!(expected, n.divisors().unwrap());
+            }
+            assert_eq!(vec![1, 65537], 65537.divisors().unwrap());
+            assert_eq!(vec![1, Num::MAX], Num::MAX.divisors().unwrap());
+            let powers: Vec<Num> = (0..=30).map(|k| 1 << k).collect();
+            assert_eq!(powers, (1 << 30).divisors().unwrap());
+            assert_eq!(
+                vec![1, 46327, 46349, 46349 * 46327],
+                (46349 * 46327).divisors().unwrap()
+            );
+        }
+        #[test]
+        fn test_euler_phi() {
+            assert!(0.euler_phi().is_err());
+            assert!((-12).euler_phi().is_err());
+            assert_eq!(1, 1.euler_phi().unwrap());
+            assert_eq!(1, 2.euler_phi().unwrap());
+            assert_eq!(4, 12.euler_phi().unwrap());
+            assert_eq!(24, 72.euler_phi().unwrap());
+            assert_eq!(70, 71.euler_phi().unwrap());
+            assert_eq!(198, 199.euler_phi().unwrap());
+            for n in 1..500 {
+                let count = (1..=n).filter(|k: &Num| k.gcd(n) == 1).count();
+                assert_eq!(count as Num, n.euler_phi().unwrap());
+            }
+        }
     }
     mod test_field {
         use super::*;
+        use crate::rng::*;
 
         #[test]
         fn test_mod_pow() {
@@ -196,6 +588,149 @@ mod test {
             assert_eq!(0, 0.mod_pow(9, 10).unwrap());
         }
         #[test]
+        fn test_mod_pow_range() {
+            let mut rng = XorShift::new(0x5eed);
+            for _ in 0..10_000 {
+                let base = rng.next_u32() as Num;
+                let exp = rng.below(Num::MAX);
+                let modulo = 1 + rng.below(Num::MAX);
+                let res = base.mod_pow(exp, modulo).unwrap();
+                assert!((0..modulo).contains(&res));
+            }
+            for _ in 0..10_000 {
+                let modulo = 1 + rng.below(50);
+                let res = (rng.next_u32() as Num)
+                    .mod_pow(rng.below(50), modulo)
+                    .unwrap();
+                assert!((0..modulo).contains(&res));
+            }
+        }
+        #[test]
+        fn test_mod_pow_negative_base() {
+            assert_eq!(1, (-6).mod_pow(0, 5).unwrap());
+            assert_eq!(0, (-5).mod_pow(3, 5).unwrap());
+            assert_eq!(0, (-10).mod_pow(1, 5).unwrap());
+            assert_eq!(0, 6.mod_pow(2, 6).unwrap());
+            assert_eq!(0, (-12).mod_pow(7, 6).unwrap());
+            assert_eq!(0, (-3).mod_pow(4, 1).unwrap());
+            assert_eq!(6, (-1).mod_pow(3, 7).unwrap());
+            assert_eq!(1, (-1).mod_pow(4, 7).unwrap());
+            for base in -30..30 {
+                for exp in 0..12 {
+                    let mut expected: Num = 1;
+                    for _ in 0..exp {
+                        expected = (expected * base).rem_euclid(13);
+                    }
+                    assert_eq!(expected.rem_euclid(13), base.mod_pow(exp, 13).unwrap());
+                }
+            }
+            assert_eq!(1, (Num::MAX - 1).mod_pow(Num::MAX - 1, Num::MAX).unwrap());
+        }
+        #[test]
+        fn test_mod_inverse() {
+            assert!(3.mod_inverse(0).is_err());
+            assert!(4.mod_inverse(6).is_err());
+            assert!(0.mod_inverse(7).is_err());
+            assert_eq!(0, 5.mod_inverse(1).unwrap());
+            assert_eq!(5, 3.mod_inverse(7).unwrap());
+            assert_eq!(2, (-3).mod_inverse(7).unwrap());
+            assert_eq!(7, 7.mod_inverse(12).unwrap());
+            for a in 1..97 {
+                let inv = a.mod_inverse(97).unwrap();
+                assert_eq!(1, (a * inv).rem_euclid(97));
+            }
+        }
+        #[test]
+        fn test_batch_inverse() {
+            assert_eq!(Vec::<Num>::new(), batch_inverse(&[], 7).unwrap());
+            assert!(batch_inverse(&[1, 2], 0).is_err());
+            assert!(batch_inverse(&[1, 0, 2], 7).is_err());
+            assert!(batch_inverse(&[1, 14, 2], 7).is_err());
+            assert!(batch_inverse(&[3, 4], 6).is_err());
+            let values: Vec<Num> = (-50..50).filter(|v: &Num| v.rem_euclid(97) != 0).collect();
+            let expected: Vec<Num> = values.iter().map(|v| v.mod_inverse(97).unwrap()).collect();
+            assert_eq!(expected, batch_inverse(&values, 97).unwrap());
+            assert_eq!(vec![5], batch_inverse(&[3], 7).unwrap());
+            assert_eq!(vec![1, 7, 5], batch_inverse(&[1, 7, 5], 12).unwrap());
+            let values = [Num::MAX - 1, 1 << 30, -123_456_789, 99_999];
+            let expected: Vec<Num> = values
+                .iter()
+                .map(|v| v.mod_inverse(Num::MAX).unwrap())
+                .collect();
+            assert_eq!(expected, batch_inverse(&values, Num::MAX).unwrap());
+        }
+        #[test]
+        fn test_mul_mod() {
+            assert_eq!(1, mul_mod(Num::MAX - 1, Num::MAX - 1, Num::MAX));
+            assert_eq!(Num::MAX - 1, mul_mod(-1, 1, Num::MAX));
+            assert_eq!(0, mul_mod(Num::MIN, Num::MIN, 2));
+            assert_eq!(Num::MAX - 2, add_mod(Num::MAX - 1, Num::MAX - 1, Num::MAX));
+            assert_eq!(6, add_mod(-1, 0, 7));
+        }
+        #[test]
+        fn test_legendre_batch() {
+            assert!(legendre_batch(&[1, 2], 9).is_err());
+            assert!(legendre_batch(&[1, 2], 0).is_err());
+            assert_eq!(Vec::<Num>::new(), legendre_batch(&[], 7).unwrap());
+            for p in [3, 5, 13, 71, 97] {
+                let values: Vec<Num> = (0..p).collect();
+                let expected: Vec<Num> = values.iter().map(|v| v.legendre(p).unwrap()).collect();
+                assert_eq!(expected, legendre_batch(&values, p).unwrap());
+                let shifted: Vec<Num> = (-3 * p..3 * p).collect();
+                let batch = legendre_batch(&shifted, p).unwrap();
+                for (v, l) in shifted.iter().zip(batch) {
+                    assert_eq!(v.rem_euclid(p).legendre(p).unwrap(), l);
+                    assert_eq!(v.jacobi(p).unwrap(), l);
+                }
+            }
+        }
+        #[test]
+        fn test_pow_table() {
+            assert_eq!(vec![1, 3], pow_table(3, 7, 1));
+            assert_eq!(vec![1, 3, 2, 6], pow_table(10, 7, 2));
+            assert_eq!(vec![0, 0], pow_table(5, 1, 1));
+            assert!(pow_table(3, 0, 2).is_empty());
+            assert!(pow_table(3, 7, 0).is_empty());
+            assert!(pow_table(3, 7, 17).is_empty());
+        }
+        #[test]
+        fn test_mod_pow_windowed() {
+            for (base, modulo) in [(3, 7), (-2, 6), (5, 97), (10, 5), (12345, 46337)] {
+                for window in 1..=5 {
+                    let table = pow_table(base, modulo, window);
+                    for exp in (0..300).chain([Num::MAX - 1, Num::MAX]) {
+                        assert_eq!(
+                            base.mod_pow(exp, modulo).unwrap(),
+                            base.mod_pow_windowed(exp, modulo, &table).unwrap()
+                        );
+                    }
+                }
+            }
+            let table = pow_table(3, 7, 2);
+            assert!(3.mod_pow_windowed(-1, 7, &table).is_err());
+            assert!(3.mod_pow_windowed(2, 0, &table).is_err());
+            assert!(4.mod_pow_windowed(2, 7, &table).is_err());
+            assert!(3.mod_pow_windowed(2, 11, &table).is_err());
+            assert!(3.mod_pow_windowed(2, 7, &table[..3]).is_err());
+            assert!(3.mod_pow_windowed(2, 7, &[]).is_err());
+        }
+        #[test]
+        fn test_qr_sieve() {
+            assert!(0.qr_sieve().is_err());
+            assert!(9.qr_sieve().is_err());
+            assert_eq!(vec![true, true], 2.qr_sieve().unwrap());
+            assert_eq!(
+                vec![true, true, true, false, true, false, false],
+                7.qr_sieve().unwrap()
+            );
+            for p in [3, 5, 13, 71, 97] {
+                let sieve = p.qr_sieve().unwrap();
+                for a in 1..p {
+                    assert_eq!(sieve[a as usize], a.mod_pow((p - 1) / 2, p).unwrap() == 1);
+                }
+            }
+        }
+        #[test]
         fn test_qr_mod_prime() {
             assert!((-1).qr_mod_prime().is_err());
             assert!(0.qr_mod_prime().is_err());
@@ -221,6 +756,112 @@ mod test {
             assert_eq!(-1, 2.legendre(5).unwrap());
             assert_eq!(1, 60.legendre(71).unwrap());
             assert_eq!(-1, 63.legendre(71).unwrap());
+            assert_eq!(1, (-11).legendre(71).unwrap());
+            assert_eq!(0, (-71).legendre(71).unwrap());
+            assert_eq!(1, 3.legendre(2).unwrap());
+            assert_eq!(-1, 5.legendre(1_000_000_007).unwrap());
+        }
+        #[test]
+        fn test_jacobi() {
+            assert!(3.jacobi(0).is_err());
+            assert!(3.jacobi(-5).is_err());
+            assert!(3.jacobi(8).is_err());
+            for p in 3..60 {
+                if !p.is_prime().unwrap() {
+                    continue;
+                }
+                for a in 0..p {
+                    assert_eq!(a.legendre(p).unwrap(), a.jacobi(p).unwrap());
+                }
+            }
+            assert_eq!(1, 2.jacobi(15).unwrap());
+            assert_eq!(-1, 7.jacobi(15).unwrap());
+            assert_eq!(0, 5.jacobi(15).unwrap());
+            assert_eq!(0, 9.jacobi(15).unwrap());
+            assert_eq!(14.jacobi(15).unwrap(), (-1).jacobi(15).unwrap());
+            assert_eq!(1, 7.jacobi(1).unwrap());
+        }
+        #[test]
+        fn test_mult_order() {
+            assert_eq!(6, 3.mult_order(7).unwrap());
+            assert_eq!(3, 2.mult_order(7).unwrap());
+            assert_eq!(1, 1.mult_order(7).unwrap());
+            assert_eq!(2, (-1).mult_order(7).unwrap());
+            assert_eq!(1, 1.mult_order(2).unwrap());
+            assert_eq!(10, 2.mult_order(11).unwrap());
+            assert!(7.mult_order(7).is_err());
+            assert!(0.mult_order(7).is_err());
+            assert!(2.mult_order(8).is_err());
+        }
+        #[test]
+        fn test_primitive_root() {
+            assert!((-7).primitive_root().is_err());
+            assert!(0.primitive_root().is_err());
+            assert!(1.primitive_root().is_err());
+            assert!(9.primitive_root().is_err());
+            assert_eq!(1, 2.primitive_root().unwrap());
+            assert_eq!(2, 3.primitive_root().unwrap());
+            assert_eq!(3, 7.primitive_root().unwrap());
+            assert_eq!(2, 11.primitive_root().unwrap());
+            assert_eq!(7, 71.primitive_root().unwrap());
+            for p in [5, 13, 47, 97] {
+                let g = p.primitive_root().unwrap();
+                assert_eq!(p - 1, g.mult_order(p).unwrap());
+            }
+        }
+        #[test]
+        fn test_sqrt_mod_prime() {
+            assert!(4.sqrt_mod_prime(9).is_err());
+            assert!(4.sqrt_mod_prime(0).is_err());
+            assert_eq!(Some(0), 0.sqrt_mod_prime(7).unwrap());
+            assert_eq!(Some(1), 3.sqrt_mod_prime(2).unwrap());
+            assert_eq!(None, 3.sqrt_mod_prime(7).unwrap());
+            for (a, p) in [(5, 998_244_353), (3, Num::MAX)] {
+                let r = mul_mod(a, a, p).sqrt_mod_prime(p).unwrap().unwrap();
+                assert_eq!(mul_mod(a, a, p), mul_mod(r, r, p));
+            }
+            for p in [3, 5, 7, 13, 17, 41, 71, 97] {
+                for a in 0..p {
+                    match a.sqrt_mod_prime(p).unwrap() {
+                        Some(r) => assert_eq!(a, r.mod_pow(2, p).unwrap()),
+                        None => assert_eq!(-1, a.legendre(p).unwrap()),
+                    }
+                }
+            }
+        }
+        #[test]
+        fn test_sqrts_mod_prime() {
+            assert!(4.sqrts_mod_prime(9).is_err());
+            assert_eq!(vec![0], 0.sqrts_mod_prime(7).unwrap());
+            assert_eq!(vec![0], 7.sqrts_mod_prime(7).unwrap());
+            assert_eq!(vec![1], 1.sqrts_mod_prime(2).unwrap());
+            assert_eq!(vec![2, 3], (-1).sqrts_mod_prime(5).unwrap());
+            assert_eq!(Vec::<Num>::new(), 3.sqrts_mod_prime(7).unwrap());
+            for p in [3, 5, 7, 13, 17, 41, 71, 97] {
+                for a in 1..p {
+                    let roots = a.sqrts_mod_prime(p).unwrap();
+                    match a.legendre(p).unwrap() {
+                        1 => {
+                            assert_eq!(2, roots.len());
+                            assert!(roots[0] < roots[1]);
+                            assert_eq!(p, roots[0] + roots[1]);
+                        }
+                        _ => assert!(roots.is_empty()),
+                    }
+                    for r in roots {
+                        assert_eq!(a, r.mod_pow(2, p).unwrap());
+                    }
+                }
+            }
+        }
+        #[test]
+        fn test_is_quadratic_residue() {
+            assert!(4.is_quadratic_residue(6).is_err());
+            assert!(4.is_quadratic_residue(5).unwrap());
+            assert!(!2.is_quadratic_residue(5).unwrap());
+            assert!(60.is_quadratic_residue(71).unwrap());
+            assert!(!63.is_quadratic_residue(71).unwrap());
+            assert!(!0.is_quadratic_residue(71).unwrap());
         }
     }
 }
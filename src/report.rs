@@ -0,0 +1,172 @@
+use crate::curve::*;
+use crate::group::*;
+use crate::prime::*;
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+
+pub const INVALID_REPORT: &str = "not a curve report...";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CurveReport {
+    pub coef1: Num,
+    pub coef0: Num,
+    pub prime: Num,
+    pub order: Num,
+    pub trace: Num,
+    pub j_invariant: Num,
+    pub discriminant: Num,
+    pub decomposition: (Num, Num),
+}
+
+impl CurveReport {
+    /// One flat JSON object in field order. This is not a general serializer;
+    /// every value is an integer, so nothing needs escaping.
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"coef1\":{},\"coef0\":{},\"prime\":{},\"order\":{},\"trace\":{},\"j_invariant\":{},\"discriminant\":{},\"decomposition\":[{},{}]}}",
+            self.coef1,
+            self.coef0,
+            self.prime,
+            self.order,
+            self.trace,
+            self.j_invariant,
+            self.discriminant,
+            self.decomposition.0,
+            self.decomposition.1,
+        )
+    }
+    /// Reads back exactly the layout `to_json` writes, rejecting anything else.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let invalid = || INVALID_REPORT.to_string();
+        let take = |rest: &str, key: &str, end: char| -> Result<(Num, usize), String> {
+            let value = rest.strip_prefix(key).ok_or_else(invalid)?;
+            let (num, tail) = value.split_once(end).ok_or_else(invalid)?;
+            let digits = num.strip_prefix('-').unwrap_or(num);
+            if digits.is_empty() || !digits.bytes().all(|c| c.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            Ok((num.parse().map_err(|_| invalid())?, json.len() - tail.len()))
+        };
+        let keys = [
+            ("{\"coef1\":", ','),
+            ("\"coef0\":", ','),
+            ("\"prime\":", ','),
+            ("\"order\":", ','),
+            ("\"trace\":", ','),
+            ("\"j_invariant\":", ','),
+            ("\"discriminant\":", ','),
+            ("\"decomposition\":[", ','),
+            ("", ']'),
+        ];
+        let mut values = [0; 9];
+        let mut pos = 0;
+        for (value, (key, end)) in values.iter_mut().zip(keys) {
+            (*value, pos) = take(&json[pos..], key, end)?;
+        }
+        if &json[pos..] != "}" {
+            return Err(invalid());
+        }
+        let [coef1, coef0, prime, order, trace, j_invariant, discriminant, n1, n2] = values;
+        Ok(CurveReport {
+            coef1,
+            coef0,
+            prime,
+            order,
+            trace,
+            j_invariant,
+            discriminant,
+            decomposition: (n1, n2),
+        })
+    }
+}
+
+pub trait Report {
+    fn report(&self) -> Result<CurveReport, String>;
+}
+
+impl Report for EllipticCurve {
+    fn report(&self) -> Result<CurveReport, String> {
+        Ok(CurveReport {
+            coef1: self.coef1(),
+            coef0: self.coef0(),
+            prime: self.prime(),
+            order: self.order()?,
+            trace: self.trace()?,
+            j_invariant: self.j_invariant()?,
+            discriminant: self.discriminant()?,
+            decomposition: self.decomposition()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod test_report {
+        use super::*;
+
+        #[test]
+        fn test_report() {
+            let curve = EllipticCurve::new(-1, 0, 71).unwrap();
+            let report = curve.report().unwrap();
+            assert_eq!(curve.coef1(), report.coef1);
+            assert_eq!(curve.coef0(), report.coef0);
+            assert_eq!(curve.prime(), report.prime);
+            assert_eq!(curve.order().unwrap(), report.order);
+            assert_eq!(curve.trace().unwrap(), report.trace);
+            assert_eq!(curve.j_invariant().unwrap(), report.j_invariant);
+            assert_eq!(curve.discriminant().unwrap(), report.discriminant);
+            assert_eq!(curve.decomposition().unwrap(), report.decomposition);
+            assert!(EllipticCurve::new(-3, 2, 71).unwrap().report().is_err());
+        }
+        #[test]
+        fn test_to_json() {
+            let report = EllipticCurve::new(-1, 0, 71).unwrap().report().unwrap();
+            assert_eq!(
+                "{\"coef1\":70,\"coef0\":0,\"prime\":71,\"order\":72,\"trace\":0,\"j_invariant\":24,\"discriminant\":64,\"decomposition\":[2,36]}",
+                report.to_json()
+            );
+        }
+        #[test]
+        fn test_from_json() {
+            for (a, b, p) in [(-1, 0, 71), (1, 6, 11), (57, 97, 199), (2, 3, 1009)] {
+                let report = EllipticCurve::new(a, b, p).unwrap().report().unwrap();
+                assert_eq!(
+                    Ok(report.clone()),
+                    CurveReport::from_json(&report.to_json())
+                );
+            }
+            let report = CurveReport {
+                coef1: -1,
+                coef0: Num::MIN,
+                prime: Num::MAX,
+                order: 0,
+                trace: -7,
+                j_invariant: 1728,
+                discriminant: 1,
+                decomposition: (1, Num::MAX),
+            };
+            assert_eq!(
+                Ok(report.clone()),
+                CurveReport::from_json(&report.to_json())
+            );
+            let json = report.to_json();
+            for bad in [
+                "",
+                "{}",
+                &json[..json.len() - 1],
+                &format!("{} ", json),
+                &json.replace("\"order\"", "\"ord\""),
+                &json.replace(":-7,", ":+7,"),
+                &json.replace(":-7,", ":\"-7\","),
+                &json.replace(":1728,", ":99999999999,"),
+                &json.replace(":1728,", ":,"),
+            ] {
+                assert_eq!(Err(INVALID_REPORT.to_string()), CurveReport::from_json(bad));
+            }
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use crate::prime::*;
+
+pub trait Rng {
+    fn next_u32(&mut self) -> u32;
+    /// `next_u32() % bound`, so the values below `2^32 % bound` are favoured,
+    /// each by at most a relative `bound / 2^32`: fine for sampling points,
+    /// not for key generation. Panics when `bound <= 0`.
+    fn below(&mut self, bound: Num) -> Num {
+        assert!(bound > 0, "bound must be positive");
+        (self.next_u32() % bound as u32) as Num
+    }
+    fn coin(&mut self) -> bool {
+        self.next_u32() & 1 == 1
+    }
+}
+
+pub struct XorShift {
+    state: u32,
+}
+
+impl XorShift {
+    pub fn new(seed: u32) -> Self {
+        XorShift {
+            state: match seed {
+                0 => 0x9e37_79b9,
+                _ => seed,
+            },
+        }
+    }
+}
+impl Rng for XorShift {
+    fn next_u32(&mut self) -> u32 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 17;
+        self.state ^= self.state << 5;
+        self.state
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod test_rng {
+        use super::*;
+
+        #[test]
+        fn test_below() {
+            let mut rng = XorShift::new(0);
+            for bound in 1..100 {
+                let n = rng.below(bound);
+                assert!((0..bound).contains(&n));
+            }
+            for _ in 0..100 {
+                assert!((0..Num::MAX).contains(&rng.below(Num::MAX)));
+            }
+        }
+        #[test]
+        #[should_panic(expected = "bound must be positive")]
+        fn test_below_zero() {
+            XorShift::new(1).below(0);
+        }
+        #[test]
+        #[should_panic(expected = "bound must be positive")]
+        fn test_below_negative() {
+            XorShift::new(1).below(-7);
+        }
+        #[test]
+        fn test_seed() {
+            let mut rng0 = XorShift::new(42);
+            let mut rng1 = XorShift::new(42);
+            for _ in 0..100 {
+                assert_eq!(rng0.next_u32(), rng1.next_u32());
+            }
+        }
+    }
+}
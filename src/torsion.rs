@@ -0,0 +1,227 @@
+use crate::curve::*;
+use crate::group::*;
+use crate::prime::*;
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+pub trait Torsion {
+    /// Coefficients of ψ_n (lowest degree first), divided by `y` when `n` is
+    /// even so that the result is a polynomial in `x` alone.
+    fn division_polynomial(&self, n: Num) -> Result<Vec<Num>, String>;
+    fn torsion_points(&self, n: Num) -> Result<Vec<Point>, String>;
+    fn torsion_structure(&self, n: Num) -> Result<(Num, Num), String>;
+}
+
+fn poly_trim(mut poly: Vec<Num>) -> Vec<Num> {
+    while poly.last() == Some(&0) {
+        poly.pop();
+    }
+    poly
+}
+fn poly_sub(lhs: &[Num], rhs: &[Num], prime: Num) -> Vec<Num> {
+    let mut res = vec![0; core::cmp::max(lhs.len(), rhs.len())];
+    for (i, c) in lhs.iter().enumerate() {
+        res[i] = *c;
+    }
+    for (i, c) in rhs.iter().enumerate() {
+        res[i] = (res[i] - c).rem_euclid(prime);
+    }
+    poly_trim(res)
+}
+fn poly_mul(lhs: &[Num], rhs: &[Num], prime: Num) -> Vec<Num> {
+    if lhs.is_empty() || rhs.is_empty() {
+        return vec![];
+    }
+    let mut res = vec![0; lhs.len() + rhs.len() - 1];
+    for (i, a) in lhs.iter().enumerate() {
+        for (j, b) in rhs.iter().enumerate() {
+            res[i + j] = add_mod(res[i + j], mul_mod(*a, *b, prime), prime);
+        }
+    }
+    poly_trim(res)
+}
+fn poly_eval(poly: &[Num], x: Num, prime: Num) -> Num {
+    poly.iter()
+        .rev()
+        .fold(0, |acc, c| add_mod(mul_mod(acc, x, prime), *c, prime))
+}
+
+impl Torsion for EllipticCurve {
+    fn division_polynomial(&self, n: Num) -> Result<Vec<Num>, String> {
+        if n < 0 {
+            return Err(NOT_AN_NON_NEG.to_string());
+        }
+        let p = self.prime();
+        let (a, b) = (self.coef1(), self.coef0());
+        let (aa, ab) = (mul_mod(a, a, p), mul_mod(a, b, p));
+        let f = vec![b, a, 0, 1];
+        let ff = poly_mul(&f, &f, p);
+        let reduce = |poly: Vec<Num>| poly_trim(poly.iter().map(|c| c.rem_euclid(p)).collect());
+        let mut psi = vec![
+            vec![],
+            vec![1],
+            reduce(vec![2]),
+            reduce(vec![-aa, mul_mod(12, b, p), mul_mod(6, a, p), 0, 3]),
+            reduce(vec![
+                add_mod(
+                    mul_mod(-32, mul_mod(b, b, p), p),
+                    mul_mod(-4, mul_mod(aa, a, p), p),
+                    p,
+                ),
+                mul_mod(-16, ab, p),
+                mul_mod(-20, aa, p),
+                mul_mod(80, b, p),
+                mul_mod(20, a, p),
+                0,
+                4,
+            ]),
+        ];
+        let half = 2.mod_inverse(p)?;
+        for k in psi.len() as Num..=n {
+            let m = (k / 2) as usize;
+            let cube = |i: usize| poly_mul(&psi[i], &poly_mul(&psi[i], &psi[i], p), p);
+            let next = if k % 2 == 1 {
+                let lhs = poly_mul(&psi[m + 2], &cube(m), p);
+                let rhs = poly_mul(&psi[m - 1], &cube(m + 1), p);
+                match m % 2 {
+                    0 => poly_sub(&poly_mul(&ff, &lhs, p), &rhs, p),
+                    _ => poly_sub(&lhs, &poly_mul(&ff, &rhs, p), p),
+                }
+            } else {
+                let lhs = poly_mul(&psi[m + 2], &poly_mul(&psi[m - 1], &psi[m - 1], p), p);
+                let rhs = poly_mul(&psi[m - 2], &poly_mul(&psi[m + 1], &psi[m + 1], p), p);
+                poly_mul(&poly_mul(&psi[m], &poly_sub(&lhs, &rhs, p), p), &[half], p)
+            };
+            psi.push(next);
+        }
+        Ok(psi.swap_remove(n as usize))
+    }
+    fn torsion_points(&self, n: Num) -> Result<Vec<Point>, String> {
+        if n < 1 {
+            return Err(NOT_A_POS.to_string());
+        }
+        let psi = self.division_polynomial(n)?;
+        let mut points = vec![Point::Inf];
+        for x in 0..self.prime() {
+            let rhs = self.rhs(x)?;
+            if poly_eval(&psi, x, self.prime()) != 0 && !(n % 2 == 0 && rhs == 0) {
+                continue;
+            }
+            for y in rhs.sqrts_mod_prime(self.prime())? {
+                points.push(Point::Affine(x, y));
+            }
+        }
+        Ok(points)
+    }
+    fn torsion_structure(&self, n: Num) -> Result<(Num, Num), String> {
+        if n < 1 {
+            return Err(NOT_A_POS.to_string());
+        }
+        if n.gcd(self.prime()) != 1 {
+            return Err(NOT_COPRIME.to_string());
+        }
+        let (n1, n2) = self.decomposition()?;
+        Ok((n.gcd(n1), n.gcd(n2)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod test_torsion {
+        use super::*;
+
+        #[test]
+        fn test_division_polynomial() {
+            let curve = EllipticCurve::new(2, 3, 97).unwrap();
+            assert!(curve.division_polynomial(-1).is_err());
+            assert_eq!(Vec::<Num>::new(), curve.division_polynomial(0).unwrap());
+            assert_eq!(vec![1], curve.division_polynomial(1).unwrap());
+            assert_eq!(vec![2], curve.division_polynomial(2).unwrap());
+            assert_eq!(
+                vec![93, 36, 12, 0, 3],
+                curve.division_polynomial(3).unwrap()
+            );
+            assert_eq!(13, curve.division_polynomial(5).unwrap().len());
+            assert_eq!(17, curve.division_polynomial(6).unwrap().len());
+            assert_eq!(25, curve.division_polynomial(7).unwrap().len());
+        }
+        #[test]
+        fn test_torsion_points() {
+            for (a, b, p) in [(-1, 0, 71), (3, 11, 53), (1, 6, 11), (2, 3, 97)] {
+                let curve = EllipticCurve::new(a, b, p).unwrap();
+                assert!(curve.torsion_points(0).is_err());
+                let sols = curve.solutions().unwrap();
+                for n in 1..=12 {
+                    let torsion = curve.torsion_points(n).unwrap();
+                    for pt in &torsion {
+                        assert!(curve.mul(n, *pt).unwrap().is_inf());
+                    }
+                    let expected: Vec<Point> = sols
+                        .iter()
+                        .copied()
+                        .filter(|pt| curve.mul(n, *pt).unwrap().is_inf())
+                        .collect();
+                    assert_eq!(expected, torsion);
+                }
+            }
+        }
+        #[test]
+        fn test_large_prime() {
+            let p = 50021;
+            let curve = EllipticCurve::new(-3, -5, p).unwrap();
+            assert_eq!(
+                vec![p - 9, p - 60, p - 18, 0, 3],
+                curve.division_polynomial(3).unwrap()
+            );
+            for (a, b) in [(-3, -5), (2, 3)] {
+                let curve = EllipticCurve::new(a, b, p).unwrap();
+                // `solutions` is quadratic in `p`; `points_with_x` is not.
+                let sols: Vec<Point> = core::iter::once(Point::Inf)
+                    .chain((0..p).flat_map(|x| curve.points_with_x(x).unwrap()))
+                    .collect();
+                for n in 1..=6 {
+                    let expected: Vec<Point> = sols
+                        .iter()
+                        .copied()
+                        .filter(|pt| curve.mul(n, *pt).unwrap().is_inf())
+                        .collect();
+                    assert_eq!(expected, curve.torsion_points(n).unwrap());
+                }
+            }
+        }
+        #[test]
+        fn test_torsion_structure() {
+            let curve = EllipticCurve::new(-1, 0, 71).unwrap();
+            assert!(curve.torsion_structure(0).is_err());
+            assert!(curve.torsion_structure(71).is_err());
+            assert!(curve.torsion_structure(142).is_err());
+            assert_eq!((2, 2), curve.torsion_structure(2).unwrap());
+            assert_eq!((2, 4), curve.torsion_structure(8).unwrap());
+            assert_eq!((1, 9), curve.torsion_structure(9).unwrap());
+            for (a, b, p) in [(-1, 0, 71), (3, 11, 53), (-1, 0, 47), (2, 3, 97)] {
+                let curve = EllipticCurve::new(a, b, p).unwrap();
+                let sols = curve.solutions().unwrap();
+                for n in 1..=24 {
+                    let (t1, t2) = curve.torsion_structure(n).unwrap();
+                    let torsion: Vec<Point> = sols
+                        .iter()
+                        .copied()
+                        .filter(|pt| curve.mul(n, *pt).unwrap().is_inf())
+                        .collect();
+                    let max_order = torsion
+                        .iter()
+                        .map(|pt| curve.cyclic_group(*pt).unwrap().len() as Num)
+                        .max()
+                        .unwrap();
+                    assert_eq!(t1 * t2, torsion.len() as Num);
+                    assert_eq!(t2, max_order);
+                }
+            }
+        }
+    }
+}
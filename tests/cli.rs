@@ -0,0 +1,71 @@
+use std::process::{Command, Output};
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_elliptic_curve"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn test_order() {
+    let output = run(&["order", "-1", "0", "71"]);
+    assert!(output.status.success());
+    assert_eq!("72\n", stdout(&output));
+}
+
+#[test]
+fn test_sum() {
+    let output = run(&["sum", "3", "11", "53", "9", "5", "38", "47"]);
+    assert!(output.status.success());
+    assert_eq!("(19, 39)\n", stdout(&output));
+    let output = run(&["sum", "-1", "0", "71", "0", "0", "0", "0"]);
+    assert!(output.status.success());
+    assert_eq!("Inf\n", stdout(&output));
+}
+
+#[test]
+fn test_mul() {
+    let output = run(&["mul", "3", "11", "53", "19", "38", "47"]);
+    assert!(output.status.success());
+    assert_eq!("Inf\n", stdout(&output));
+    let output = run(&["mul", "3", "11", "53", "1", "38", "47"]);
+    assert_eq!("(38, 47)\n", stdout(&output));
+}
+
+#[test]
+fn test_points() {
+    let output = run(&["points", "1", "6", "11"]);
+    assert!(output.status.success());
+    let out = stdout(&output);
+    assert_eq!(13, out.lines().count());
+    assert_eq!(Some("Inf"), out.lines().next());
+}
+
+#[test]
+fn test_demo() {
+    let output = run(&["demo"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).starts_with("Elliptic curve: "));
+}
+
+#[test]
+fn test_errors() {
+    for args in [
+        &[][..],
+        &["frobnicate"][..],
+        &["order", "1", "2"][..],
+        &["order", "1", "x", "7"][..],
+        &["order", "1", "2", "8"][..],
+        &["sum", "3", "11", "53", "9", "6", "38", "47"][..],
+    ] {
+        let output = run(args);
+        assert!(!output.status.success());
+        assert!(output.stdout.is_empty());
+        assert!(!output.stderr.is_empty());
+    }
+}